@@ -36,6 +36,7 @@ impl FdbScope {
     fn gen_impl<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
         writeln!(w, "impl {name} {{", name = self.name)?;
         self.gen_code(w)?;
+        self.gen_param(w)?;
         self.gen_apply(w)?;
         writeln!(w, "}}")
     }
@@ -73,6 +74,33 @@ impl FdbScope {
         writeln!(w, "{t}}}", t = TAB1)
     }
 
+    fn gen_param<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
+        if !self.with_ty() {
+            return Ok(());
+        }
+
+        writeln!(
+            w,
+            "{t}/// The parameter of the option, as it is passed to the C API.",
+            t = TAB1
+        )?;
+        writeln!(w, "{t}pub fn param(&self) -> Vec<u8> {{", t = TAB1)?;
+        writeln!(w, "{t}match *self {{", t = TAB2)?;
+
+        for option in self.options.iter() {
+            write!(w, "{}{}::{}", TAB3, self.name, option.name)?;
+            match option.param_type {
+                FdbOptionTy::Empty => writeln!(w, " => Vec::new(),")?,
+                FdbOptionTy::Int => writeln!(w, "(v) => (v as i64).to_le_bytes().to_vec(),")?,
+                FdbOptionTy::Bytes => writeln!(w, "(ref v) => v.clone(),")?,
+                FdbOptionTy::Str => writeln!(w, "(ref v) => v.as_bytes().to_vec(),")?,
+            }
+        }
+
+        writeln!(w, "{t}}}", t = TAB2)?;
+        writeln!(w, "{t}}}", t = TAB1)
+    }
+
     fn gen_apply<W: fmt::Write>(&self, w: &mut W) -> fmt::Result {
         let fn_name = match self.apply_fn_name() {
             Some(name) => name,
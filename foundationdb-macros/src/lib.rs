@@ -4,7 +4,7 @@ use quote::quote;
 use std::collections::HashMap;
 use syn::__private::TokenStream2;
 use syn::parse::Parser;
-use syn::{Item, ItemFn, LitInt};
+use syn::{Data, DeriveInput, Fields, Item, ItemFn, LitInt};
 use try_map::FallibleMapExt;
 
 /// Allow to compute the range of supported api versions for a functionality.
@@ -130,6 +130,165 @@ pub fn simulation_entrypoint(_attr: TokenStream, item: TokenStream) -> TokenStre
     .into()
 }
 
+/// Derives `foundationdb::tuple::TuplePack` for a struct.
+///
+/// The fields are packed in declaration order, exactly like a tuple of the same fields would be.
+/// Fields marked with `#[tuple(skip)]` are left out.
+#[proc_macro_derive(TuplePack, attributes(tuple))]
+pub fn derive_tuple_pack(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    tuple_pack_impl(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Derives `foundationdb::tuple::TupleUnpack` for a struct.
+///
+/// The fields are unpacked in declaration order, exactly like a tuple of the same fields would
+/// be. Fields marked with `#[tuple(skip)]` are not read and are set to their `Default` value.
+#[proc_macro_derive(TupleUnpack, attributes(tuple))]
+pub fn derive_tuple_unpack(input: TokenStream) -> TokenStream {
+    let input = syn::parse_macro_input!(input as DeriveInput);
+    tuple_unpack_impl(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// A struct field taking part in the tuple encoding.
+struct TupleField<'a> {
+    member: syn::Member,
+    ty: &'a syn::Type,
+    skip: bool,
+}
+
+fn tuple_fields(input: &DeriveInput) -> syn::Result<Vec<TupleField<'_>>> {
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "tuple encoding can only be derived for structs",
+            ))
+        }
+    };
+    let fields = match fields {
+        Fields::Named(fields) => &fields.named,
+        Fields::Unnamed(fields) => &fields.unnamed,
+        Fields::Unit => return Ok(Vec::new()),
+    };
+
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let mut skip = false;
+            for attr in field
+                .attrs
+                .iter()
+                .filter(|attr| attr.path().is_ident("tuple"))
+            {
+                attr.parse_nested_meta(|meta| {
+                    if meta.path.is_ident("skip") {
+                        skip = true;
+                        Ok(())
+                    } else {
+                        Err(meta.error("unsupported tuple property"))
+                    }
+                })?;
+            }
+            let member = match &field.ident {
+                Some(ident) => syn::Member::Named(ident.clone()),
+                None => syn::Member::Unnamed(index.into()),
+            };
+            Ok(TupleField {
+                member,
+                ty: &field.ty,
+                skip,
+            })
+        })
+        .collect()
+}
+
+fn tuple_pack_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = tuple_fields(&input)?;
+    let packed: Vec<_> = fields.iter().filter(|field| !field.skip).collect();
+    let members = packed.iter().map(|field| &field.member);
+    let bounds = packed.iter().map(|field| {
+        let ty = field.ty;
+        quote::quote_spanned!(syn::spanned::Spanned::span(ty)=> #ty: ::foundationdb::tuple::TuplePack)
+    });
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|clause| &clause.predicates);
+
+    Ok(quote!(
+        impl #impl_generics ::foundationdb::tuple::TuplePack for #name #ty_generics
+        where
+            #(#bounds,)*
+            #predicates
+        {
+            fn pack<W: ::std::io::Write>(
+                &self,
+                w: &mut W,
+                tuple_depth: ::foundationdb::tuple::TupleDepth,
+            ) -> ::std::io::Result<::foundationdb::tuple::VersionstampOffset> {
+                ::foundationdb::tuple::TuplePack::pack(&(#(&self.#members,)*), w, tuple_depth)
+            }
+        }
+    ))
+}
+
+fn tuple_unpack_impl(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = tuple_fields(&input)?;
+    let unpacked: Vec<_> = fields.iter().filter(|field| !field.skip).collect();
+    let bindings: Vec<_> = (0..unpacked.len())
+        .map(|index| quote::format_ident!("field_{}", index))
+        .collect();
+    let types = unpacked.iter().map(|field| field.ty);
+    let bounds = unpacked.iter().map(|field| {
+        let ty = field.ty;
+        quote::quote_spanned!(syn::spanned::Spanned::span(ty)=> #ty: ::foundationdb::tuple::TupleUnpack<'__de>)
+    });
+    let mut next_binding = bindings.iter();
+    let initializers = fields.iter().map(|field| {
+        let member = &field.member;
+        if field.skip {
+            quote!(#member: ::std::default::Default::default())
+        } else {
+            let binding = next_binding.next();
+            quote!(#member: #binding)
+        }
+    });
+
+    let name = &input.ident;
+    let mut generics = input.generics.clone();
+    generics.params.insert(0, syn::parse_quote!('__de));
+    let (impl_generics, _, _) = generics.split_for_impl();
+    let (_, ty_generics, where_clause) = input.generics.split_for_impl();
+    let predicates = where_clause.map(|clause| &clause.predicates);
+
+    Ok(quote!(
+        impl #impl_generics ::foundationdb::tuple::TupleUnpack<'__de> for #name #ty_generics
+        where
+            #(#bounds,)*
+            #predicates
+        {
+            fn unpack(
+                input: &'__de [u8],
+                tuple_depth: ::foundationdb::tuple::TupleDepth,
+            ) -> ::foundationdb::tuple::PackResult<(&'__de [u8], Self)> {
+                let (input, (#(#bindings,)*)) =
+                    <(#(#types,)*) as ::foundationdb::tuple::TupleUnpack<'__de>>::unpack(
+                        input,
+                        tuple_depth,
+                    )?;
+                Ok((input, Self { #(#initializers,)* }))
+            }
+        }
+    ))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::cfg_api_versions_impl;
@@ -14,20 +14,107 @@
 //! - [Network](https://apple.github.io/foundationdb/api-c.html#network)
 
 use std::panic;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI32, Ordering};
 use std::sync::{Arc, Condvar, Mutex};
 use std::thread;
 
 use crate::options::NetworkOption;
-use crate::{error, FdbResult};
+use crate::{error, FdbError, FdbResult};
+use foundationdb_macros::cfg_api_versions;
 use foundationdb_sys as fdb_sys;
 
+/// `invalid_option_value` error code
+const INVALID_OPTION_VALUE: i32 = 2006;
+/// `api_version_not_supported` error code
+pub(crate) const API_VERSION_NOT_SUPPORTED: i32 = 2203;
+
 /// Returns the max api version of the underlying Fdb C API Client
 pub fn get_max_api_version() -> i32 {
     unsafe { fdb_sys::fdb_get_max_api_version() }
 }
 
 static VERSION_SELECTED: AtomicBool = AtomicBool::new(false);
+static SELECTED_API_VERSION: AtomicI32 = AtomicI32::new(0);
+
+/// Returns the runtime API version selected through `FdbApiBuilder::build`
+///
+/// Returns `None` if no version was selected yet, or if it was selected without going through
+/// `FdbApiBuilder`, like in a simulation workload.
+pub fn selected_api_version() -> Option<i32> {
+    match SELECTED_API_VERSION.load(Ordering::Acquire) {
+        0 => None,
+        version => Some(version),
+    }
+}
+
+/// Fails with `api_version_not_supported` if the selected runtime API version is older than
+/// `min_version`. Nothing is checked when the selected version is unknown.
+#[cfg_api_versions(min = 710)]
+pub(crate) fn ensure_api_version(min_version: i32) -> FdbResult<()> {
+    match selected_api_version() {
+        Some(version) if version < min_version => Err(FdbError::new(API_VERSION_NOT_SUPPORTED)),
+        _ => Ok(()),
+    }
+}
+
+/// The API versions supported by this crate
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ApiVersion {
+    /// API version of FoundationDB 5.1
+    V510 = 510,
+    /// API version of FoundationDB 5.2
+    V520 = 520,
+    /// API version of FoundationDB 6.0
+    V600 = 600,
+    /// API version of FoundationDB 6.1
+    V610 = 610,
+    /// API version of FoundationDB 6.2
+    V620 = 620,
+    /// API version of FoundationDB 6.3
+    V630 = 630,
+    /// API version of FoundationDB 7.0
+    V700 = 700,
+    /// API version of FoundationDB 7.1
+    V710 = 710,
+    /// API version of FoundationDB 7.3
+    V730 = 730,
+}
+
+impl ApiVersion {
+    const ALL: [ApiVersion; 9] = [
+        ApiVersion::V510,
+        ApiVersion::V520,
+        ApiVersion::V600,
+        ApiVersion::V610,
+        ApiVersion::V620,
+        ApiVersion::V630,
+        ApiVersion::V700,
+        ApiVersion::V710,
+        ApiVersion::V730,
+    ];
+
+    /// The version number, as given to `fdb_select_api_version`
+    pub fn code(self) -> i32 {
+        self as i32
+    }
+
+    /// Returns the greatest version supported by both the client library, as reported by
+    /// `fdb_get_max_api_version`, and the headers this crate is built against.
+    pub fn max_supported() -> Option<ApiVersion> {
+        Self::latest_up_to(get_max_api_version().min(fdb_sys::FDB_API_VERSION as i32))
+    }
+
+    fn latest_up_to(max: i32) -> Option<ApiVersion> {
+        Self::ALL.iter().rev().copied().find(|v| v.code() <= max)
+    }
+
+    fn ensure_supported(self, max: Option<ApiVersion>) -> FdbResult<()> {
+        match max {
+            Some(max) if self <= max => Ok(()),
+            _ => Err(FdbError::new(API_VERSION_NOT_SUPPORTED)),
+        }
+    }
+}
 
 /// A Builder with which different versions of the Fdb C API can be initialized
 ///
@@ -55,6 +142,16 @@ impl FdbApiBuilder {
         self
     }
 
+    /// Set the version of run-time behavior the API is requested to provide.
+    ///
+    /// Unlike `set_runtime_version`, the version is checked against `ApiVersion::max_supported`
+    /// and `api_version_not_supported` is returned if it is too recent, instead of failing
+    /// later in `build`.
+    pub fn set_api_version(self, version: ApiVersion) -> FdbResult<Self> {
+        version.ensure_supported(ApiVersion::max_supported())?;
+        Ok(self.set_runtime_version(version.code()))
+    }
+
     /// Initialize the foundationDB API and returns a `NetworkBuilder`
     ///
     /// # Panics
@@ -73,6 +170,7 @@ impl FdbApiBuilder {
                 fdb_sys::FDB_API_VERSION as i32,
             )
         })?;
+        SELECTED_API_VERSION.store(self.runtime_version, Ordering::Release);
         Ok(NetworkBuilder { _private: () })
     }
 }
@@ -108,6 +206,17 @@ impl NetworkBuilder {
         Ok(self)
     }
 
+    /// Set all the network options accumulated by a `NetworkOptionBuilder`.
+    ///
+    /// If any of them is invalid, an `invalid_option_value` error is returned and none of them
+    /// is set.
+    pub fn set_options(self, options: NetworkOptionBuilder) -> FdbResult<Self> {
+        options
+            .into_options()?
+            .into_iter()
+            .try_fold(self, |builder, option| builder.set_option(option))
+    }
+
     /// Finalizes the initialization of the Network and returns a way to run/wait/stop the
     /// FoundationDB run loop.
     ///
@@ -207,6 +316,127 @@ impl NetworkBuilder {
     }
 }
 
+/// A typed builder for the most common network options.
+///
+/// Each method checks the value it is given, so that mistakes are reported before any option
+/// is set. Options are only applied, all at once, by `NetworkOptionBuilder::build_and_run` or
+/// `NetworkBuilder::set_options`.
+///
+/// ```
+/// use foundationdb::api::{FdbApiBuilder, NetworkOptionBuilder};
+///
+/// let network_builder = FdbApiBuilder::default().build().expect("fdb api initialized");
+/// let network = unsafe {
+///     NetworkOptionBuilder::new()
+///         .trace_enable("/tmp")
+///         .knob("min_trace_severity", "10")
+///         .build_and_run(network_builder)
+/// }
+/// .expect("fdb network running");
+/// // do some interesting things with the API...
+/// drop(network);
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct NetworkOptionBuilder {
+    options: Vec<NetworkOption>,
+    invalid: bool,
+}
+
+impl NetworkOptionBuilder {
+    /// Creates a builder without any option.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn push_if(mut self, valid: bool, option: NetworkOption) -> Self {
+        if valid {
+            self.options.push(option);
+        } else {
+            self.invalid = true;
+        }
+        self
+    }
+
+    /// Enables trace output to a file in the given directory.
+    pub fn trace_enable(self, directory: impl Into<String>) -> Self {
+        self.push_if(true, NetworkOption::TraceEnable(directory.into()))
+    }
+
+    /// Sets the `LogGroup` attribute of the trace events.
+    pub fn trace_log_group(self, group: impl Into<String>) -> Self {
+        let group = group.into();
+        self.push_if(!group.is_empty(), NetworkOption::TraceLogGroup(group))
+    }
+
+    /// Selects the format of the trace files, either `xml` or `json`.
+    #[cfg_api_versions(min = 610)]
+    pub fn trace_format(self, format: impl Into<String>) -> Self {
+        let format = format.into();
+        self.push_if(
+            format == "xml" || format == "json",
+            NetworkOption::TraceFormat(format),
+        )
+    }
+
+    /// Sets an internal tuning or debugging knob.
+    ///
+    /// The name must not be empty nor contain `=`.
+    pub fn knob(self, name: &str, value: &str) -> Self {
+        self.push_if(
+            !name.is_empty() && !name.contains('='),
+            NetworkOption::Knob(format!("{}={}", name, value)),
+        )
+    }
+
+    /// Sets the path of the file containing the TLS certificate.
+    pub fn tls_cert_path(self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.push_if(!path.is_empty(), NetworkOption::TLSCertPath(path))
+    }
+
+    /// Sets the path of the file containing the TLS private key.
+    pub fn tls_key_path(self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.push_if(!path.is_empty(), NetworkOption::TLSKeyPath(path))
+    }
+
+    /// Sets the path of the file containing the CA certificates chain.
+    #[cfg_api_versions(min = 520)]
+    pub fn tls_ca_path(self, path: impl Into<String>) -> Self {
+        let path = path.into();
+        self.push_if(!path.is_empty(), NetworkOption::TLSCaPath(path))
+    }
+
+    /// Adds an option that has no typed method.
+    pub fn option(self, option: NetworkOption) -> Self {
+        self.push_if(true, option)
+    }
+
+    /// Returns the accumulated options, or an `invalid_option_value` error if any of them was
+    /// given an invalid value.
+    pub fn into_options(self) -> FdbResult<Vec<NetworkOption>> {
+        if self.invalid {
+            Err(FdbError::new(INVALID_OPTION_VALUE))
+        } else {
+            Ok(self.options)
+        }
+    }
+
+    /// Sets all the options then starts the FoundationDB run loop in a dedicated thread, see
+    /// `NetworkBuilder::boot`.
+    ///
+    /// # Safety
+    ///
+    /// You *MUST* ensure `drop` is called on the returned object before the program exits.
+    /// This is not required if the program is aborted.
+    pub unsafe fn build_and_run(
+        self,
+        network_builder: NetworkBuilder,
+    ) -> FdbResult<NetworkAutoStop> {
+        network_builder.set_options(self)?.boot()
+    }
+}
+
 /// A foundationDB network event loop runner
 ///
 /// Most of the time you should never need to use this directly and use `boot()`.
@@ -291,7 +521,11 @@ impl NetworkStop {
 /// Stop the associated `NetworkRunner` and thread if dropped
 ///
 /// If trying to stop the FoundationDB run loop results in an error.
-/// The error is printed in `stderr` and the process aborts.
+/// The error is printed in `stderr` and the process aborts. Use `NetworkAutoStop::stop` to
+/// handle that error instead.
+///
+/// Once stopped, the network cannot be started again in the same process: this is a limitation
+/// of the FoundationDB client, and no FoundationDB API can be used afterwards.
 ///
 /// # Panics
 ///
@@ -300,9 +534,34 @@ pub struct NetworkAutoStop {
     network: Option<NetworkStop>,
     handle: Option<std::thread::JoinHandle<()>>,
 }
+
+impl NetworkAutoStop {
+    /// Stops the FoundationDB run loop and waits for the network thread to finish.
+    ///
+    /// This is what dropping the handle does, except that a failure to stop the run loop is
+    /// returned instead of aborting the process. In that case the network thread is left
+    /// running, detached.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the network thread cannot be joined.
+    pub fn stop(mut self) -> FdbResult<()> {
+        let result = self.network.take().unwrap().stop();
+        let handle = self.handle.take().unwrap();
+        if result.is_ok() {
+            handle.join().expect("failed to join fdb thread");
+        }
+        result
+    }
+}
+
 impl Drop for NetworkAutoStop {
     fn drop(&mut self) {
-        if let Err(err) = self.network.take().unwrap().stop() {
+        // already stopped through `NetworkAutoStop::stop`
+        let Some(network) = self.network.take() else {
+            return;
+        };
+        if let Err(err) = network.stop() {
             eprintln!("failed to stop network: {}", err);
             // Not aborting can probably cause undefined behavior
             std::process::abort();
@@ -323,4 +582,47 @@ mod tests {
     fn test_max_api() {
         assert!(get_max_api_version() > 0);
     }
+
+    #[test]
+    fn test_api_version() {
+        assert!(ApiVersion::max_supported().is_some());
+        assert_eq!(ApiVersion::latest_up_to(720), Some(ApiVersion::V710));
+        assert_eq!(ApiVersion::latest_up_to(730), Some(ApiVersion::V730));
+        assert_eq!(ApiVersion::latest_up_to(500), None);
+
+        let max = ApiVersion::latest_up_to(710);
+        assert!(ApiVersion::V710.ensure_supported(max).is_ok());
+        let err = ApiVersion::V730.ensure_supported(max).unwrap_err();
+        assert_eq!(err.code(), API_VERSION_NOT_SUPPORTED);
+        assert!(ApiVersion::V510.ensure_supported(None).is_err());
+
+        for version in ApiVersion::ALL {
+            let result = FdbApiBuilder::default().set_api_version(version);
+            if Some(version) > ApiVersion::max_supported() {
+                assert!(result.is_err());
+            } else {
+                assert_eq!(result.unwrap().runtime_version(), version.code());
+            }
+        }
+    }
+
+    #[test]
+    fn test_network_option_builder() {
+        let options = NetworkOptionBuilder::new()
+            .trace_enable("/tmp")
+            .knob("min_trace_severity", "10")
+            .into_options()
+            .unwrap();
+        assert_eq!(options.len(), 2);
+        assert!(
+            matches!(&options[1], NetworkOption::Knob(knob) if knob == "min_trace_severity=10")
+        );
+
+        let err = NetworkOptionBuilder::new()
+            .trace_enable("/tmp")
+            .knob("", "10")
+            .into_options()
+            .unwrap_err();
+        assert_eq!(err.code(), INVALID_OPTION_VALUE);
+    }
 }
@@ -0,0 +1,131 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A capped exponential backoff with jitter.
+//!
+//! `Backoff` is not used by the retry loops of `Database::run` and `Database::transact`: their
+//! delay is computed and waited by the FoundationDB client inside `Transaction::on_error`, and
+//! cannot be replaced from the bindings. `Backoff` is meant for the other places where the
+//! bindings, or their users, need to wait before trying again: re-arming a watch, polling the
+//! cluster until it is ready...
+
+use std::time::Duration;
+
+use futures::Future;
+use rand::Rng;
+
+use crate::timer;
+
+/// An exponential backoff, capped and with jitter.
+///
+/// The n-th call to `next_delay` returns `base * 2^n`, capped to `max`, minus a random part of
+/// up to `jitter` times that value.
+///
+/// ```
+/// use foundationdb::Backoff;
+/// use std::time::Duration;
+///
+/// let mut backoff = Backoff::new(Duration::from_millis(10), Duration::from_secs(1), 0.0);
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(10));
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(20));
+/// assert_eq!(backoff.next_delay(), Duration::from_millis(40));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    base: Duration,
+    max: Duration,
+    jitter: f64,
+    attempt: u32,
+}
+
+impl Default for Backoff {
+    /// Starts at 10ms and is capped to 1s, like the FoundationDB client retry loop, with a 50%
+    /// jitter.
+    fn default() -> Self {
+        Self::new(Duration::from_millis(10), Duration::from_secs(1), 0.5)
+    }
+}
+
+impl Backoff {
+    /// Creates a new backoff.
+    ///
+    /// `jitter` is the fraction of each delay that may be randomly removed from it, it is
+    /// clamped to `[0, 1]`.
+    pub fn new(base: Duration, max: Duration, jitter: f64) -> Self {
+        Self {
+            base,
+            max,
+            jitter: if jitter.is_nan() {
+                0.0
+            } else {
+                jitter.clamp(0.0, 1.0)
+            },
+            attempt: 0,
+        }
+    }
+
+    /// Returns the delay to wait before the next attempt, and moves on to the following one.
+    pub fn next_delay(&mut self) -> Duration {
+        let delay = self
+            .base
+            .checked_mul(1 << self.attempt.min(31))
+            .map_or(self.max, |delay| delay.min(self.max));
+        self.attempt = self.attempt.saturating_add(1);
+
+        if self.jitter > 0.0 {
+            delay.mul_f64(1.0 - rand::thread_rng().gen_range(0.0..=self.jitter))
+        } else {
+            delay
+        }
+    }
+
+    /// Waits for the next delay.
+    pub fn wait(&mut self) -> impl Future<Output = ()> + Send + Sync + Unpin {
+        timer::delay(self.next_delay())
+    }
+
+    /// Starts over from the base delay, typically after a successful attempt.
+    pub fn reset(&mut self) {
+        self.attempt = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exponential() {
+        let mut backoff = Backoff::new(Duration::from_millis(1), Duration::from_secs(60), 0.0);
+        for i in 0..10 {
+            assert_eq!(backoff.next_delay(), Duration::from_millis(1 << i));
+        }
+        backoff.reset();
+        assert_eq!(backoff.next_delay(), Duration::from_millis(1));
+    }
+
+    #[test]
+    fn test_capped() {
+        let max = Duration::from_millis(100);
+        let mut backoff = Backoff::new(Duration::from_millis(1), max, 0.0);
+        for _ in 0..100 {
+            assert!(backoff.next_delay() <= max);
+        }
+        assert_eq!(backoff.next_delay(), max);
+    }
+
+    #[test]
+    fn test_jitter() {
+        let mut backoff =
+            Backoff::new(Duration::from_millis(100), Duration::from_millis(100), 0.25);
+        let delays: Vec<Duration> = (0..100).map(|_| backoff.next_delay()).collect();
+        assert!(delays
+            .iter()
+            .all(|d| *d >= Duration::from_millis(75) && *d <= Duration::from_millis(100)));
+        assert!(delays.iter().any(|d| *d != delays[0]));
+    }
+}
@@ -0,0 +1,141 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Bulk loading through a sequence of size-bounded transactions.
+
+use crate::{Database, FdbResult, Transaction};
+
+/// Transactions are committed once they reach this approximate size, well below the 10MB hard
+/// limit of FoundationDB.
+const DEFAULT_MAX_TRANSACTION_SIZE: usize = 8 * 1024 * 1024;
+const DEFAULT_MAX_COUNT: usize = 100_000;
+
+/// Writes key-value pairs through as many transactions as needed.
+///
+/// Pairs are accumulated into a transaction which is committed, and retried on retryable
+/// errors, as soon as its approximate size reaches the configured maximum or it holds the
+/// configured number of pairs. Each commit is independent: pairs written before a failed commit
+/// stay written.
+///
+/// Pairs not yet committed are lost if the writer is dropped, call `finish` once done.
+///
+/// ```no_run
+/// # async fn load(db: &foundationdb::Database) -> foundationdb::FdbResult<()> {
+/// use foundationdb::BatchWriter;
+///
+/// let mut writer = BatchWriter::new(db).set_max_transaction_size(1024 * 1024);
+/// for i in 0..1_000_000u32 {
+///     writer.write(&i.to_be_bytes(), b"value").await?;
+/// }
+/// let written = writer.finish().await?;
+/// assert_eq!(written, 1_000_000);
+/// # Ok(())
+/// # }
+/// ```
+pub struct BatchWriter<'db> {
+    db: &'db Database,
+    trx: Option<Transaction>,
+    pending: Vec<(Vec<u8>, Vec<u8>)>,
+    unchecked_bytes: usize,
+    max_transaction_size: usize,
+    max_count: usize,
+    written: u64,
+}
+
+impl<'db> BatchWriter<'db> {
+    /// Creates a writer committing transactions of about 8MB or 100000 pairs.
+    pub fn new(db: &'db Database) -> Self {
+        Self {
+            db,
+            trx: None,
+            pending: Vec::new(),
+            unchecked_bytes: 0,
+            max_transaction_size: DEFAULT_MAX_TRANSACTION_SIZE,
+            max_count: DEFAULT_MAX_COUNT,
+            written: 0,
+        }
+    }
+
+    /// Sets the approximate size, in bytes, at which a transaction is committed.
+    pub fn set_max_transaction_size(mut self, max_transaction_size: usize) -> Self {
+        self.max_transaction_size = max_transaction_size.max(1);
+        self
+    }
+
+    /// Sets the number of pairs at which a transaction is committed.
+    pub fn set_max_count(mut self, max_count: usize) -> Self {
+        self.max_count = max_count.max(1);
+        self
+    }
+
+    /// Returns the number of pairs committed so far.
+    pub fn written(&self) -> u64 {
+        self.written
+    }
+
+    /// Adds a key-value pair to the current transaction, committing it if it is full.
+    pub async fn write(&mut self, key: &[u8], value: &[u8]) -> FdbResult<()> {
+        let trx = match self.trx.take() {
+            Some(trx) => trx,
+            None => self.db.create_trx()?,
+        };
+        trx.set(key, value);
+        let trx = self.trx.insert(trx);
+        self.pending.push((key.to_vec(), value.to_vec()));
+
+        if self.pending.len() >= self.max_count {
+            return self.flush().await;
+        }
+
+        // the size is only asked for every few writes, the transaction may overshoot the
+        // maximum by a sixteenth of it
+        self.unchecked_bytes += key.len() + value.len();
+        if self.unchecked_bytes >= self.max_transaction_size / 16 {
+            self.unchecked_bytes = 0;
+            let size = trx.get_approximate_size().await?;
+            if size as usize >= self.max_transaction_size {
+                return self.flush().await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Commits the current transaction, retrying it until it succeeds or fails with a
+    /// non-retryable error.
+    pub async fn flush(&mut self) -> FdbResult<()> {
+        self.unchecked_bytes = 0;
+        let Some(mut trx) = self.trx.take() else {
+            return Ok(());
+        };
+        loop {
+            match trx.commit().await {
+                Ok(_) => break,
+                Err(err) => {
+                    trx = match err.on_error().await {
+                        Ok(trx) => trx,
+                        Err(err) => {
+                            self.pending.clear();
+                            return Err(err);
+                        }
+                    };
+                    for (key, value) in self.pending.iter() {
+                        trx.set(key, value);
+                    }
+                }
+            }
+        }
+        self.written += self.pending.len() as u64;
+        self.pending.clear();
+        Ok(())
+    }
+
+    /// Commits the pairs left and returns the total number of pairs written.
+    pub async fn finish(mut self) -> FdbResult<u64> {
+        self.flush().await?;
+        Ok(self.written)
+    }
+}
@@ -13,7 +13,9 @@
 use std::convert::TryFrom;
 use std::future::Future;
 use std::ptr::NonNull;
+use std::sync::Arc;
 
+use crate::database::DatabaseHandle;
 use crate::future::*;
 use crate::{error, Database, FdbError, FdbResult};
 use foundationdb_sys as fdb_sys;
@@ -93,8 +95,9 @@ impl TryFrom<FdbFutureHandle> for Database {
         error::eval(unsafe { fdb_sys::fdb_future_get_database(f.as_ptr(), &mut v) })?;
 
         Ok(Database {
-            inner: NonNull::new(v)
-                .expect("fdb_future_get_database to not return null if there is no error"),
+            inner: Arc::new(DatabaseHandle::new(NonNull::new(v).expect(
+                "fdb_future_get_database to not return null if there is no error",
+            ))),
         })
     }
 }
@@ -0,0 +1,71 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! 64-bit counters maintained with atomic additions.
+
+use futures::{Future, FutureExt};
+
+use crate::{FdbResult, Transaction};
+
+/// A signed 64-bit counter stored at a single key.
+///
+/// The value is a little-endian integer updated with `MutationType::Add`, so concurrent
+/// increments never conflict with each other. A missing key reads as zero.
+///
+/// ```no_run
+/// # async fn count(trx: &foundationdb::Transaction) -> foundationdb::FdbResult<()> {
+/// use foundationdb::{tuple::Subspace, Counter};
+///
+/// let hits = Counter::new(Subspace::from("stats").pack(&"hits"));
+/// hits.increment(trx, 1);
+/// let total = hits.read(trx, false).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Counter {
+    key: Vec<u8>,
+}
+
+impl Counter {
+    /// Creates a counter stored at `key`.
+    pub fn new(key: impl Into<Vec<u8>>) -> Self {
+        Self { key: key.into() }
+    }
+
+    /// Returns the key the counter is stored at.
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    /// Atomically adds `delta`, which may be negative, to the counter.
+    pub fn increment(&self, trx: &Transaction, delta: i64) {
+        trx.add_i64(&self.key, delta)
+    }
+
+    /// Reads the value of the counter, zero if it was never incremented.
+    ///
+    /// A non-snapshot read conflicts with every concurrent increment, use a snapshot read when
+    /// an approximate value is good enough.
+    pub fn read(
+        &self,
+        trx: &Transaction,
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<i64>> + Send + Sync + Unpin {
+        trx.get(&self.key, snapshot)
+            .map(|value| Ok(value?.map_or(0, |value| decode(&value))))
+    }
+}
+
+/// Decodes a little-endian integer, shorter values are zero-extended and longer ones truncated
+/// like `MutationType::Add` does.
+fn decode(value: &[u8]) -> i64 {
+    let mut bytes = [0; 8];
+    let len = value.len().min(8);
+    bytes[..len].copy_from_slice(&value[..len]);
+    i64::from_le_bytes(bytes)
+}
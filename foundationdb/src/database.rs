@@ -14,12 +14,14 @@ use std::convert::TryInto;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::ptr::NonNull;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use foundationdb_macros::cfg_api_versions;
 use foundationdb_sys as fdb_sys;
 
 use crate::options;
+use crate::trace;
 use crate::transaction::*;
 use crate::{error, FdbError, FdbResult};
 
@@ -30,6 +32,9 @@ use futures::prelude::*;
 #[cfg(feature = "tenant-experimental")]
 use crate::tenant::FdbTenant;
 
+/// The transaction_too_old error code.
+const TRANSACTION_TOO_OLD: i32 = 1007;
+
 /// Wrapper around the boolean representing whether the
 /// previous transaction is still on fly
 /// This wrapper prevents the boolean to be copy and force it
@@ -49,15 +54,33 @@ impl From<MaybeCommitted> for bool {
 /// A mutable, lexicographically ordered mapping from binary keys to binary values.
 ///
 /// Modifications to a database are performed via transactions.
+///
+/// A `Database` is meant to be opened once and shared: cloning it is cheap and every clone uses
+/// the same native database object, and thus the same connection to the cluster and the same
+/// options. The native object is destroyed when the last clone is dropped.
+#[derive(Clone)]
 pub struct Database {
-    pub(crate) inner: NonNull<fdb_sys::FDBDatabase>,
+    pub(crate) inner: Arc<DatabaseHandle>,
+}
+
+/// Owns a native database object.
+pub(crate) struct DatabaseHandle(NonNull<fdb_sys::FDBDatabase>);
+
+impl DatabaseHandle {
+    pub(crate) const fn new(ptr: NonNull<fdb_sys::FDBDatabase>) -> Self {
+        Self(ptr)
+    }
+
+    pub(crate) const fn as_ptr(&self) -> *mut fdb_sys::FDBDatabase {
+        self.0.as_ptr()
+    }
 }
-unsafe impl Send for Database {}
-unsafe impl Sync for Database {}
-impl Drop for Database {
+unsafe impl Send for DatabaseHandle {}
+unsafe impl Sync for DatabaseHandle {}
+impl Drop for DatabaseHandle {
     fn drop(&mut self) {
         unsafe {
-            fdb_sys::fdb_database_destroy(self.inner.as_ptr());
+            fdb_sys::fdb_database_destroy(self.as_ptr());
         }
     }
 }
@@ -83,7 +106,9 @@ impl Database {
 
     /// Create a new FDBDatabase from a raw pointer. Users are expected to use the `new` method.
     pub fn new_from_pointer(ptr: NonNull<fdb_sys::FDBDatabase>) -> Self {
-        Self { inner: ptr }
+        Self {
+            inner: Arc::new(DatabaseHandle::new(ptr)),
+        }
     }
 
     /// Create a database for the given configuration path
@@ -91,11 +116,127 @@ impl Database {
         Self::new(Some(path))
     }
 
+    /// Create a database for the cluster file at `path`, checking first that it can be read.
+    ///
+    /// Unlike `from_path`, a missing or unreadable file, or a path that is not a regular file, is
+    /// reported with its path, as a `FdbBindingError::CustomError` holding an `io::Error`, rather
+    /// than as the FDB error raised when the client fails to connect. Errors raised by FDB itself
+    /// are returned as a `FdbBindingError::NonRetryableFdbError`.
+    pub fn open_cluster_file(path: &std::path::Path) -> Result<Database, FdbBindingError> {
+        use std::io;
+
+        let custom_error = |kind, message: String| {
+            FdbBindingError::new_custom_error(Box::new(io::Error::new(kind, message)))
+        };
+        let readable = std::fs::metadata(path).and_then(|metadata| {
+            std::fs::File::open(path)?;
+            Ok(metadata.is_file())
+        });
+        match readable {
+            Ok(true) => {}
+            // a directory can be opened on linux, it would only fail once the client reads it
+            Ok(false) => {
+                return Err(custom_error(
+                    io::ErrorKind::InvalidInput,
+                    format!("cluster file {} is not a file", path.display()),
+                ))
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(custom_error(
+                    io::ErrorKind::NotFound,
+                    format!("cluster file not found: {}", path.display()),
+                ))
+            }
+            Err(err) => {
+                return Err(custom_error(
+                    err.kind(),
+                    format!("cannot read cluster file {}: {}", path.display(), err),
+                ))
+            }
+        }
+        let path_str = path.to_str().ok_or_else(|| {
+            custom_error(
+                io::ErrorKind::InvalidInput,
+                format!("cluster file path is not valid UTF-8: {}", path.display()),
+            )
+        })?;
+        Ok(Self::new(Some(path_str))?)
+    }
+
     /// Create a database for the default configuration path
     #[allow(clippy::should_implement_trait)]
     pub fn default() -> FdbResult<Database> {
         Self::new(None)
     }
+
+    /// Create a database from a connection string (`description:ID@IP:PORT,...`) instead of a
+    /// cluster file.
+    ///
+    /// Fails with `connection_string_invalid` (2104) if the string contains a NUL byte.
+    #[cfg_api_versions(min = 730)]
+    pub fn from_connection_string(connection_string: &str) -> FdbResult<Database> {
+        // connection_string_invalid
+        let connection_string =
+            std::ffi::CString::new(connection_string).map_err(|_| FdbError::new(2104))?;
+        let mut v: *mut fdb_sys::FDBDatabase = std::ptr::null_mut();
+        let err = unsafe {
+            fdb_sys::fdb_create_database_from_connection_string(connection_string.as_ptr(), &mut v)
+        };
+        error::eval(err)?;
+        let ptr = NonNull::new(v).expect(
+            "fdb_create_database_from_connection_string to not return null if there is no error",
+        );
+        Ok(Self::new_from_pointer(ptr))
+    }
+
+    /// Create a database from a connection string (`description:ID@IP:PORT,...`) instead of a
+    /// cluster file.
+    ///
+    /// This API version cannot take a connection string directly, so it is written to a new
+    /// cluster file `fdb-<pid>-<random>.cluster` in `std::env::temp_dir()`, created exclusively
+    /// and, on Unix, readable and writable by the current user only. The file is never removed
+    /// by the bindings, as the client may update it when the coordinators change: it belongs to
+    /// the caller, who may remove these files once the process is done with the database.
+    ///
+    /// Fails with `connection_string_invalid` (2104) if the string contains a NUL byte, and with
+    /// `file_not_writable` (1514) if the cluster file cannot be created.
+    #[cfg_api_versions(min = 610, max = 710)]
+    pub fn from_connection_string(connection_string: &str) -> FdbResult<Database> {
+        use std::io::Write;
+
+        if connection_string.contains('\0') {
+            // connection_string_invalid
+            return Err(FdbError::new(2104));
+        }
+
+        let mut options = std::fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let dir = std::env::temp_dir();
+        let (path, mut file) = loop {
+            let path = dir.join(format!(
+                "fdb-{}-{:016x}.cluster",
+                std::process::id(),
+                rand::random::<u64>()
+            ));
+            match options.open(&path) {
+                Ok(file) => break (path, file),
+                Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+                // file_not_writable
+                Err(_) => return Err(FdbError::new(1514)),
+            }
+        };
+        file.write_all(connection_string.as_bytes())
+            .map_err(|_| FdbError::new(1514))?;
+        drop(file);
+
+        Self::new(Some(
+            path.to_str()
+                .expect("temporary directory to be valid utf-8"),
+        ))
+    }
 }
 
 #[cfg_api_versions(min = 710)]
@@ -120,9 +261,64 @@ impl Database {
     }
 }
 
+#[cfg_api_versions(min = 700)]
+impl Database {
+    /// Reboots the worker process listening on `address`, e.g. `"127.0.0.1:4500"`.
+    ///
+    /// **WARNING**: this is an administrative operation acting on the cluster itself, not on
+    /// data. Rebooting a worker interrupts every role it hosts, and is meant for cluster
+    /// operations tooling only.
+    ///
+    /// # Arguments
+    ///
+    /// * `address`: the address of the worker, as listed in the cluster status
+    /// * `check`: if `true`, the worker checks the integrity of its data files when restarting
+    /// * `duration`: if non-zero, the worker suspends for that many seconds before restarting
+    ///
+    /// Returns `1` if the reboot was requested, `0` if no worker matched the address.
+    pub fn reboot_worker(
+        &self,
+        address: &str,
+        check: bool,
+        duration: i32,
+    ) -> impl Future<Output = FdbResult<i64>> + Send + Sync + Unpin {
+        crate::future::FdbFuture::new(unsafe {
+            fdb_sys::fdb_database_reboot_worker(
+                self.inner.as_ptr(),
+                address.as_ptr(),
+                address.len().try_into().unwrap(),
+                check as fdb_sys::fdb_bool_t,
+                duration,
+            )
+        })
+    }
+
+    /// Forces the recovery of the cluster, using `dc_id` as the new primary datacenter.
+    ///
+    /// **WARNING**: as its name states, this operation **loses data**. Every mutation that was
+    /// not yet replicated to `dc_id` is discarded, including committed transactions. It is
+    /// meant as a last resort to restore availability when the primary datacenter is lost, and
+    /// should only be used by cluster operations tooling.
+    pub fn force_recovery_with_data_loss(
+        &self,
+        dc_id: &str,
+    ) -> impl Future<Output = FdbResult<()>> + Send + Sync + Unpin {
+        crate::future::FdbFuture::new(unsafe {
+            fdb_sys::fdb_database_force_recovery_with_data_loss(
+                self.inner.as_ptr(),
+                dc_id.as_ptr(),
+                dc_id.len().try_into().unwrap(),
+            )
+        })
+    }
+}
+
 #[cfg_api_versions(min = 730)]
 impl Database {
     /// Retrieve a client-side status information in a JSON format.
+    ///
+    /// The status reports the connection of the client to the cluster, for example its
+    /// `Healthy` field, without running a transaction, which makes it a cheap liveness probe.
     pub fn get_client_status(
         &self,
     ) -> impl Future<Output = FdbResult<crate::future::FdbSlice>> + Send + Sync + Unpin {
@@ -158,13 +354,22 @@ impl Database {
 
     /// Creates a new transaction on the given database.
     pub fn create_trx(&self) -> FdbResult<Transaction> {
-        let mut trx: *mut fdb_sys::FDBTransaction = std::ptr::null_mut();
-        let err =
-            unsafe { fdb_sys::fdb_database_create_transaction(self.inner.as_ptr(), &mut trx) };
-        error::eval(err)?;
-        Ok(Transaction::new(NonNull::new(trx).expect(
-            "fdb_database_create_transaction to not return null if there is no error",
-        )))
+        trace::create_trx(|| {
+            let mut trx: *mut fdb_sys::FDBTransaction = std::ptr::null_mut();
+            let err =
+                unsafe { fdb_sys::fdb_database_create_transaction(self.inner.as_ptr(), &mut trx) };
+            error::eval(err)?;
+            Ok(Transaction::new(NonNull::new(trx).expect(
+                "fdb_database_create_transaction to not return null if there is no error",
+            )))
+        })
+    }
+
+    /// Creates a new transaction on the given database, with `options` already set.
+    pub fn create_trx_with_options(&self, options: &TransactionOptions) -> FdbResult<Transaction> {
+        let trx = self.create_trx()?;
+        options.apply(&trx)?;
+        Ok(trx)
     }
 
     fn create_retryable_trx(&self) -> FdbResult<RetryableTransaction> {
@@ -280,6 +485,26 @@ impl Database {
         )
     }
 
+    /// Runs a boxed transactional function with the retry logic of `transact`, using the default
+    /// `TransactOption`.
+    ///
+    /// Unlike `transact_boxed`, both the function and the returned future are trait objects, so
+    /// transaction bodies with different concrete types can be stored side by side, e.g. in a
+    /// `Vec`, and run later.
+    #[allow(clippy::type_complexity)]
+    pub fn run_boxed<'db, T>(
+        &'db self,
+        f: Box<
+            dyn for<'t> Fn(&'t Transaction) -> future::BoxFuture<'t, FdbResult<T>> + Send + Sync,
+        >,
+    ) -> future::BoxFuture<'db, FdbResult<T>>
+    where
+        T: Send + 'db,
+    {
+        self.transact_boxed(f, |trx, f| f(trx), TransactOption::default())
+            .boxed()
+    }
+
     /// Runs a transactional function against this Database with retry logic.
     /// The associated closure will be called until a non-retryable FDBError
     /// is thrown or commit(), returns success.
@@ -314,16 +539,36 @@ impl Database {
         F: Fn(RetryableTransaction, MaybeCommitted) -> Fut,
         Fut: Future<Output = Result<T, FdbBindingError>>,
     {
+        self.run_with_metrics(closure, &()).await
+    }
+
+    /// Same as [`Database::run`], reporting the retries, the successful commit and the final
+    /// error of the retry loop to `metrics`.
+    pub async fn run_with_metrics<F, Fut, T, M>(
+        &self,
+        closure: F,
+        metrics: &M,
+    ) -> Result<T, FdbBindingError>
+    where
+        F: Fn(RetryableTransaction, MaybeCommitted) -> Fut,
+        Fut: Future<Output = Result<T, FdbBindingError>>,
+        M: TransactionMetrics + ?Sized,
+    {
+        let start = Instant::now();
         let mut maybe_committed_transaction = false;
         // we just need to create the transaction once,
         // in case there is a error, it will be reset automatically
         let mut transaction = self.create_retryable_trx()?;
+        let mut retry_count: u32 = 0;
 
         loop {
             // executing the closure
-            let result_closure = closure(
-                transaction.clone(),
-                MaybeCommitted(maybe_committed_transaction),
+            let result_closure = trace::attempt(
+                closure(
+                    transaction.clone(),
+                    MaybeCommitted(maybe_committed_transaction),
+                ),
+                retry_count,
             )
             .await;
 
@@ -335,11 +580,14 @@ impl Database {
                     match transaction.on_error(e).await {
                         // we can retry the error
                         Ok(Ok(t)) => {
+                            metrics.on_retry(e.code());
+                            retry_count += 1;
                             transaction = t;
                             continue;
                         }
                         Ok(Err(non_retryable_error)) => {
-                            return Err(FdbBindingError::from(non_retryable_error))
+                            metrics.on_give_up(non_retryable_error.code());
+                            return Err(FdbBindingError::from(non_retryable_error));
                         }
                         // The only FdbBindingError that can be thrown here is `ReferenceToTransactionKept`
                         Err(non_retryable_error) => return Err(non_retryable_error),
@@ -354,17 +602,24 @@ impl Database {
             match commit_result {
                 // The only FdbBindingError that can be thrown here is `ReferenceToTransactionKept`
                 Err(err) => return Err(err),
-                Ok(Ok(_)) => return result_closure,
+                Ok(Ok(_)) => {
+                    metrics.on_commit(start.elapsed());
+                    return result_closure;
+                }
                 Ok(Err(transaction_commit_error)) => {
                     maybe_committed_transaction = transaction_commit_error.is_maybe_committed();
+                    let code = transaction_commit_error.code();
                     // we have an error during commit, checking if it is a retryable error
                     match transaction_commit_error.on_error().await {
                         Ok(t) => {
+                            metrics.on_retry(code);
+                            retry_count += 1;
                             transaction = RetryableTransaction::new(t);
                             continue;
                         }
                         Err(non_retryable_error) => {
-                            return Err(FdbBindingError::from(non_retryable_error))
+                            metrics.on_give_up(non_retryable_error.code());
+                            return Err(FdbBindingError::from(non_retryable_error));
                         }
                     }
                 }
@@ -388,6 +643,137 @@ impl Database {
         trx.get_read_version().await?;
         Ok(())
     }
+
+    /// Waits until the cluster serves reads, for at most `timeout`.
+    ///
+    /// This is a readiness gate for services starting before the cluster is reachable: a key
+    /// that does not need to exist is read, in a transaction timing out with the time left, and
+    /// the read is tried again after a [Backoff](crate::Backoff) delay until it succeeds. Once
+    /// `timeout` has elapsed, the error of the last attempt is returned, typically
+    /// `transaction_timed_out` (1031) if the cluster could not be reached at all.
+    pub async fn wait_for_healthy(&self, timeout: Duration) -> FdbResult<()> {
+        let deadline = Instant::now() + timeout;
+        let mut backoff = crate::Backoff::default();
+        loop {
+            let left = deadline.saturating_duration_since(Instant::now());
+            let err = match self.read_any_key(left).await {
+                Ok(()) => return Ok(()),
+                Err(err) => err,
+            };
+            let delay = backoff.next_delay();
+            if Instant::now() + delay >= deadline {
+                return Err(err);
+            }
+            crate::timer::delay(delay).await;
+        }
+    }
+
+    async fn read_any_key(&self, timeout: Duration) -> FdbResult<()> {
+        let trx = self.create_trx()?;
+        let timeout_ms = timeout.as_millis().clamp(1, i32::MAX as u128) as i32;
+        trx.set_option(options::TransactionOption::Timeout(timeout_ms))?;
+        trx.get(b"", true).await?;
+        Ok(())
+    }
+
+    /// Watches `key` for changes, yielding a notification each time its value changes.
+    ///
+    /// The first watch is armed, in its own transaction, before this returns: any change made
+    /// afterwards is reported. Each time the watch fires, a new one is armed in a fresh
+    /// transaction before the notification is yielded. Changes happening while the watch is
+    /// re-armed are coalesced in a single notification, and, as with [Transaction::watch], a
+    /// value changed and then changed back might not be reported at all.
+    ///
+    /// If the watch fails with a retryable error, it is re-armed after a [Backoff](crate::Backoff)
+    /// delay and a notification is yielded, as a change may have been missed. The first
+    /// non-retryable error ends the stream.
+    ///
+    /// Dropping the stream cancels the pending watch and frees its native future.
+    pub async fn watch_stream<'a>(
+        &'a self,
+        key: &'a [u8],
+    ) -> FdbResult<impl Stream<Item = FdbResult<()>> + Send + 'a> {
+        let watch = self.arm_watch(key).await?;
+        Ok(stream::try_unfold(
+            (watch, crate::Backoff::default()),
+            move |(watch, mut backoff)| async move {
+                match watch.await {
+                    Ok(()) => backoff.reset(),
+                    Err(err) if err.is_retryable() => backoff.wait().await,
+                    Err(err) => return Err(err),
+                }
+                let watch = self.arm_watch(key).await?;
+                Ok(Some(((), (watch, backoff))))
+            },
+        ))
+    }
+
+    /// Reads all key-value pairs of the range, page by page, with snapshot reads.
+    ///
+    /// A transaction can only read for about 5 seconds, after which reads fail with the
+    /// transaction_too_old error. With `allow_snapshot_reset`, a page failing with this error
+    /// is read again in a new transaction, at a fresh read version, and the scan resumes after
+    /// the last key already yielded. Pages read before and after the reset are then not
+    /// consistent with each other: this is meant for full scans that cannot fit in a single
+    /// transaction and can cope with concurrent changes.
+    ///
+    /// Without `allow_snapshot_reset`, or if the first page of a transaction fails, the error
+    /// is yielded and ends the stream.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `allow_snapshot_reset`: whether to resume in a new transaction when the current one
+    ///   gets too old
+    pub fn scan_range<'a>(
+        &'a self,
+        opt: RangeOption<'a>,
+        allow_snapshot_reset: bool,
+    ) -> impl Stream<Item = FdbResult<crate::future::FdbValues>> + Send + 'a {
+        stream::try_unfold(
+            (None::<Transaction>, Some(opt), 1),
+            move |(trx, opt, iteration)| async move {
+                let Some(opt) = opt else {
+                    return Ok(None);
+                };
+                let (mut trx, mut iteration) = match trx {
+                    Some(trx) => (trx, iteration),
+                    None => (self.create_trx()?, 1),
+                };
+                let values = loop {
+                    match trx.get_range(&opt, iteration, true).await {
+                        Ok(values) => break values,
+                        Err(err)
+                            if allow_snapshot_reset
+                                && err.code() == TRANSACTION_TOO_OLD
+                                && iteration > 1 =>
+                        {
+                            trx = self.create_trx()?;
+                            iteration = 1;
+                        }
+                        Err(err) => return Err(err),
+                    }
+                };
+                let next_opt = opt.next_range(&values);
+                Ok(Some((values, (Some(trx), next_opt, iteration + 1))))
+            },
+        )
+    }
+
+    /// Commits a transaction watching `key`, retrying it on retryable errors.
+    async fn arm_watch(
+        &self,
+        key: &[u8],
+    ) -> FdbResult<impl Future<Output = FdbResult<()>> + Send + Sync + Unpin> {
+        let mut trx = self.create_trx()?;
+        loop {
+            let watch = trx.watch(key);
+            match trx.commit().await {
+                Ok(_) => return Ok(watch),
+                Err(err) => trx = err.on_error().await?,
+            }
+        }
+    }
 }
 pub trait DatabaseTransact: Sized {
     type Item;
@@ -516,6 +902,23 @@ impl TransactError for FdbError {
     }
 }
 
+/// Hooks called by [`Database::run_with_metrics`] to observe its retry loop.
+///
+/// All the methods default to doing nothing, and so does the implementation for `()` used by
+/// [`Database::run`].
+pub trait TransactionMetrics {
+    /// Called when the transaction is about to be retried after the error `code`, once the
+    /// backoff of `on_error` has elapsed.
+    fn on_retry(&self, _code: i32) {}
+    /// Called when the transaction committed, `latency` is the time spent since the beginning of
+    /// the first attempt.
+    fn on_commit(&self, _latency: Duration) {}
+    /// Called when the retry loop stops because of the non-retryable error `code`.
+    fn on_give_up(&self, _code: i32) {}
+}
+
+impl TransactionMetrics for () {}
+
 /// A set of options that controls the behavior of `Database::transact`.
 #[derive(Default, Clone)]
 pub struct TransactOption {
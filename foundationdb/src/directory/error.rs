@@ -42,6 +42,7 @@ pub enum DirectoryError {
     CannotPrefixInPartition,
     /// the root directory cannot be moved
     CannotMoveRootDirectory,
+    /// cannot move a directory between different partitions
     CannotMoveBetweenPartition,
     /// the destination directory cannot be a subdirectory of the source directory
     CannotMoveBetweenSubdirectory,
@@ -28,7 +28,10 @@ pub(crate) fn eval(error_code: fdb_sys::fdb_error_t) -> FdbResult<()> {
 }
 
 /// The Standard Error type of FoundationDB
-#[derive(Debug, Copy, Clone)]
+///
+/// Both `Display` and `Debug` include the error code and the description FoundationDB gives for
+/// it. An `FdbError` never has a `source`: the code is all FoundationDB reports.
+#[derive(Copy, Clone)]
 pub struct FdbError {
     /// The FoundationDB error code
     error_code: i32,
@@ -82,14 +85,44 @@ impl FdbError {
     }
 }
 
+impl fmt::Debug for FdbError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("FdbError")
+            .field("error_code", &self.error_code)
+            .field("message", &self.message())
+            .finish()
+    }
+}
+
 impl fmt::Display for FdbError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        std::fmt::Display::fmt(&self.message(), f)
+        write!(f, "{} ({})", self.message(), self.error_code)
     }
 }
 
 impl std::error::Error for FdbError {}
 
+/// Converts into an `std::io::Error` whose payload is the original `FdbError`.
+///
+/// The error kind is:
+/// * `ErrorKind::Interrupted` if the error is retryable,
+/// * `ErrorKind::NotFound` if a file (1511), the cluster file (1515) or a tenant (2131) could not
+///   be found,
+/// * `ErrorKind::Other` for everything else.
+///
+/// The code can be recovered by downcasting the inner error to `FdbError`.
+impl From<FdbError> for std::io::Error {
+    fn from(err: FdbError) -> Self {
+        let kind = match err.code() {
+            // file_not_found | no_cluster_file_found | tenant_not_found
+            1511 | 1515 | 2131 => std::io::ErrorKind::NotFound,
+            _ if err.is_retryable() => std::io::ErrorKind::Interrupted,
+            _ => std::io::ErrorKind::Other,
+        };
+        std::io::Error::new(kind, err)
+    }
+}
+
 /// Alias for `Result<..., FdbError>`
 pub type FdbResult<T> = Result<T, FdbError>;
 
@@ -170,10 +203,45 @@ impl Debug for FdbBindingError {
     }
 }
 
+/// When the error has a `source`, only the context is displayed and the wrapped error is left to
+/// the `source` chain, so that error reporters do not print it twice. Otherwise, this is the same
+/// as `Debug`.
 impl Display for FdbBindingError {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        std::fmt::Debug::fmt(&self, f)
+        if std::error::Error::source(self).is_none() {
+            return std::fmt::Debug::fmt(&self, f);
+        }
+        match self {
+            FdbBindingError::NonRetryableFdbError(_) => write!(f, "non-retryable FDB error"),
+            FdbBindingError::HcaError(_) => write!(f, "high contention allocator error"),
+            FdbBindingError::DirectoryError(_) => write!(f, "directory error"),
+            FdbBindingError::PackError(_) => write!(f, "tuple packing error"),
+            FdbBindingError::CustomError(_) => write!(f, "custom error"),
+            FdbBindingError::ReferenceToTransactionKept => std::fmt::Debug::fmt(&self, f),
+        }
     }
 }
 
-impl std::error::Error for FdbBindingError {}
+/// The `source` is the wrapped error, if any: the `FdbError`, the `PackError`, or the custom
+/// error. For directory and allocator errors, it is the `FdbError` or `PackError` that caused
+/// them.
+impl std::error::Error for FdbBindingError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            FdbBindingError::NonRetryableFdbError(err)
+            | FdbBindingError::DirectoryError(DirectoryError::FdbError(err))
+            | FdbBindingError::HcaError(HcaError::FdbError(err))
+            | FdbBindingError::DirectoryError(DirectoryError::HcaError(HcaError::FdbError(err))) => {
+                Some(err)
+            }
+            FdbBindingError::PackError(err)
+            | FdbBindingError::DirectoryError(DirectoryError::PackError(err))
+            | FdbBindingError::HcaError(HcaError::PackError(err))
+            | FdbBindingError::DirectoryError(DirectoryError::HcaError(HcaError::PackError(err))) => {
+                Some(err)
+            }
+            FdbBindingError::CustomError(err) => Some(err.as_ref()),
+            _ => None,
+        }
+    }
+}
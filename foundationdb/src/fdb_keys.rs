@@ -42,6 +42,28 @@ impl TryFrom<FdbFutureHandle> for FdbKeys {
     }
 }
 
+impl FdbKeys {
+    /// Number of keys
+    pub fn len(&self) -> usize {
+        self.deref().len()
+    }
+
+    /// `true` if there is no key
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the first key, if any
+    pub fn first(&self) -> Option<&FdbKey> {
+        self.deref().first()
+    }
+
+    /// Returns the last key, if any
+    pub fn last(&self) -> Option<&FdbKey> {
+        self.deref().last()
+    }
+}
+
 impl Deref for FdbKeys {
     type Target = [FdbKey];
     fn deref(&self) -> &Self::Target {
@@ -142,6 +164,11 @@ impl AsRef<FdbKey> for FdbRowKey {
         self.deref()
     }
 }
+impl AsRef<[u8]> for FdbRowKey {
+    fn as_ref(&self) -> &[u8] {
+        self.key()
+    }
+}
 impl PartialEq for FdbRowKey {
     fn eq(&self, other: &Self) -> bool {
         self.deref() == other.deref()
@@ -166,6 +193,18 @@ impl FdbKey {
     }
 }
 
+impl Deref for FdbKey {
+    type Target = [u8];
+    fn deref(&self) -> &Self::Target {
+        self.key()
+    }
+}
+impl AsRef<[u8]> for FdbKey {
+    fn as_ref(&self) -> &[u8] {
+        self.key()
+    }
+}
+
 impl PartialEq for FdbKey {
     fn eq(&self, other: &Self) -> bool {
         self.key() == other.key()
@@ -22,6 +22,7 @@
 //! important for reducing the latency of transactions.
 //!
 
+use std::collections::BTreeMap;
 use std::convert::TryFrom;
 use std::ffi::CStr;
 use std::fmt;
@@ -50,6 +51,13 @@ impl FdbFutureHandle {
     pub const fn as_ptr(&self) -> *mut fdb_sys::FDBFuture {
         self.0.as_ptr()
     }
+
+    /// Cancels the future, which is then set with the operation_cancelled error.
+    ///
+    /// Cancelling a future that is already ready has no effect.
+    pub fn cancel(&self) {
+        unsafe { fdb_sys::fdb_future_cancel(self.as_ptr()) }
+    }
 }
 unsafe impl Sync for FdbFutureHandle {}
 unsafe impl Send for FdbFutureHandle {}
@@ -66,6 +74,8 @@ impl Drop for FdbFutureHandle {
 ///
 /// Non owned result type (Fdb
 pub(crate) struct FdbFuture<T> {
+    // moved into the result once resolved, so that the handle is destroyed exactly once: with
+    // the result, or with this future if it is dropped pending, which cancels it
     f: Option<FdbFutureHandle>,
     waker: Option<Arc<AtomicWaker>>,
     phantom: std::marker::PhantomData<T>,
@@ -84,6 +94,12 @@ where
             phantom: std::marker::PhantomData,
         }
     }
+    /// Cancels the underlying FDBFuture if it has not resolved yet.
+    pub(crate) fn cancel(&self) {
+        if let Some(f) = &self.f {
+            f.cancel();
+        }
+    }
 }
 
 impl<T> Future for FdbFuture<T>
@@ -124,6 +140,34 @@ where
     }
 }
 
+/// A pending watch, as returned by `Transaction::watch`.
+///
+/// Dropping it cancels the watch, `cancel` does the same without giving up the future: awaiting
+/// it then returns the operation_cancelled error.
+pub struct FdbWatch(FdbFuture<()>);
+
+impl FdbWatch {
+    pub(crate) fn new(f: *mut fdb_sys::FDBFuture) -> Self {
+        Self(FdbFuture::new(f))
+    }
+
+    /// Cancels the watch, releasing it on the server, so that it does not count against the
+    /// MAX_WATCHES limit anymore.
+    ///
+    /// Has no effect if the watch already fired or failed.
+    pub fn cancel(&self) {
+        self.0.cancel()
+    }
+}
+
+impl Future for FdbWatch {
+    type Output = FdbResult<()>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<FdbResult<()>> {
+        Pin::new(&mut self.0).poll(cx)
+    }
+}
+
 // The callback from fdb C API can be called from multiple threads. so this callback should be
 // thread-safe.
 extern "C" fn fdb_future_callback(
@@ -136,10 +180,17 @@ extern "C" fn fdb_future_callback(
 
 /// A slice of bytes owned by a foundationDB future
 pub struct FdbSlice {
-    _f: FdbFutureHandle,
+    _f: SliceOwner,
     value: *const u8,
     len: i32,
 }
+
+/// The future an `FdbSlice` points into, shared when the slice was taken out of a range read.
+#[allow(dead_code)]
+enum SliceOwner {
+    Future(FdbFutureHandle),
+    Shared(Arc<FdbFutureHandle>),
+}
 unsafe impl Sync for FdbSlice {}
 unsafe impl Send for FdbSlice {}
 
@@ -155,6 +206,91 @@ impl AsRef<[u8]> for FdbSlice {
     }
 }
 
+impl FdbSlice {
+    /// Divides the slice in two at index `mid`, without copying: the first half holds the bytes
+    /// before `mid`, the second one the bytes from `mid` on.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `mid > len`, see `split_at_checked` for a non-panicking version.
+    pub fn split_at(&self, mid: usize) -> (&[u8], &[u8]) {
+        self.split_at_checked(mid)
+            .unwrap_or_else(|| panic!("mid {} out of a slice of {} bytes", mid, self.len()))
+    }
+
+    /// Same as `split_at`, but returns `None` if `mid > len`.
+    pub fn split_at_checked(&self, mid: usize) -> Option<(&[u8], &[u8])> {
+        let bytes = self.deref();
+        if mid <= bytes.len() {
+            Some(bytes.split_at(mid))
+        } else {
+            None
+        }
+    }
+}
+
+impl PartialEq for FdbSlice {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+impl Eq for FdbSlice {}
+impl PartialEq<[u8]> for FdbSlice {
+    fn eq(&self, other: &[u8]) -> bool {
+        self.deref() == other
+    }
+}
+impl PartialEq<&[u8]> for FdbSlice {
+    fn eq(&self, other: &&[u8]) -> bool {
+        self.deref() == *other
+    }
+}
+impl<const N: usize> PartialEq<[u8; N]> for FdbSlice {
+    fn eq(&self, other: &[u8; N]) -> bool {
+        self.deref() == other
+    }
+}
+impl<const N: usize> PartialEq<&[u8; N]> for FdbSlice {
+    fn eq(&self, other: &&[u8; N]) -> bool {
+        self.deref() == *other
+    }
+}
+impl PartialEq<Vec<u8>> for FdbSlice {
+    fn eq(&self, other: &Vec<u8>) -> bool {
+        self.deref() == other.as_slice()
+    }
+}
+impl PartialEq<str> for FdbSlice {
+    fn eq(&self, other: &str) -> bool {
+        self.deref() == other.as_bytes()
+    }
+}
+impl PartialEq<&str> for FdbSlice {
+    fn eq(&self, other: &&str) -> bool {
+        self.deref() == other.as_bytes()
+    }
+}
+impl PartialOrd for FdbSlice {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for FdbSlice {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.deref().cmp(other.deref())
+    }
+}
+impl PartialOrd<[u8]> for FdbSlice {
+    fn partial_cmp(&self, other: &[u8]) -> Option<std::cmp::Ordering> {
+        self.deref().partial_cmp(other)
+    }
+}
+impl fmt::Debug for FdbSlice {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        crate::tuple::Bytes::from(self.deref()).fmt(f)
+    }
+}
+
 impl TryFrom<FdbFutureHandle> for FdbSlice {
     type Error = FdbError;
 
@@ -164,7 +300,11 @@ impl TryFrom<FdbFutureHandle> for FdbSlice {
 
         error::eval(unsafe { fdb_sys::fdb_future_get_key(f.as_ptr(), &mut value, &mut len) })?;
 
-        Ok(FdbSlice { _f: f, value, len })
+        Ok(FdbSlice {
+            _f: SliceOwner::Future(f),
+            value,
+            len,
+        })
     }
 }
 
@@ -183,7 +323,11 @@ impl TryFrom<FdbFutureHandle> for Option<FdbSlice> {
         Ok(if present == 0 {
             None
         } else {
-            Some(FdbSlice { _f: f, value, len })
+            Some(FdbSlice {
+                _f: SliceOwner::Future(f),
+                value,
+                len,
+            })
         })
     }
 }
@@ -256,6 +400,10 @@ impl AsRef<CStr> for FdbAddress {
 }
 
 /// An slice of keyvalues owned by a foundationDB future
+///
+/// The keyvalues are in the order of the range read: ascending keys, or descending keys if
+/// `RangeOption::reverse` was set. Both the borrowed and the owned iterators yield them in that
+/// order, and `next_back` walks it backward.
 pub struct FdbValues {
     _f: FdbFutureHandle,
     keyvalues: *const FdbKeyValue,
@@ -270,6 +418,54 @@ impl FdbValues {
     pub fn more(&self) -> bool {
         self.more
     }
+
+    /// Number of keyvalues
+    pub fn len(&self) -> usize {
+        self.deref().len()
+    }
+
+    /// `true` if there is no keyvalue
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the first keyvalue, in the order of the range read, if any
+    pub fn first(&self) -> Option<&FdbKeyValue> {
+        self.deref().first()
+    }
+
+    /// Returns the last keyvalue, in the order of the range read, if any
+    pub fn last(&self) -> Option<&FdbKeyValue> {
+        self.deref().last()
+    }
+
+    /// Number of bytes referenced by this result: the key-value descriptors and the key and value
+    /// bytes they point to.
+    ///
+    /// This memory is kept alive as long as this result (or any `FdbValue` taken out of it) is
+    /// held. It is a lower bound of the memory held by the future, not the size of its arena,
+    /// which the C API does not expose.
+    pub fn retained_bytes(&self) -> usize {
+        self.len() * std::mem::size_of::<FdbKeyValue>() + self.byte_len()
+    }
+
+    /// Total number of bytes of the keys and values of this result.
+    ///
+    /// Unlike `retained_bytes`, the key-value descriptors are not accounted for.
+    pub fn byte_len(&self) -> usize {
+        self.iter()
+            .map(|kv| kv.key().len() + kv.value().len())
+            .sum()
+    }
+
+    /// Copies the keyvalues into an owned map, sorted by key.
+    ///
+    /// The map is in key order even if the range was read in reverse.
+    pub fn to_btree_map(&self) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        self.iter()
+            .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
+            .collect()
+    }
 }
 
 impl TryFrom<FdbFutureHandle> for FdbValues {
@@ -414,6 +610,18 @@ pub struct FdbValue {
 
 unsafe impl Send for FdbValue {}
 
+impl FdbValue {
+    /// Turns this keyvalue into its value, still owned by the range read's future.
+    pub(crate) fn into_value(self) -> FdbSlice {
+        let value = self.value();
+        FdbSlice {
+            value: value.as_ptr(),
+            len: value.len() as i32,
+            _f: SliceOwner::Shared(self._f),
+        }
+    }
+}
+
 impl Deref for FdbValue {
     type Target = FdbKeyValue;
     fn deref(&self) -> &Self::Target {
@@ -464,6 +672,13 @@ impl FdbKeyValue {
             std::slice::from_raw_parts(self.0.value as *const u8, self.0.value_length as usize)
         }
     }
+
+    /// key and value, borrowed together
+    ///
+    /// This is the preferred accessor when both are needed, e.g. `let (key, value) = kv.pair();`.
+    pub fn pair(&self) -> (&[u8], &[u8]) {
+        (self.key(), self.value())
+    }
 }
 
 impl PartialEq for FdbKeyValue {
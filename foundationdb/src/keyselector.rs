@@ -10,6 +10,8 @@
 
 use crate::tuple::Bytes;
 use std::borrow::Cow;
+use std::fmt;
+use std::ops::{Add, Sub};
 
 /// A `KeySelector` identifies a particular key in the database.
 ///
@@ -29,7 +31,9 @@ use std::borrow::Cow;
 /// - `last_less_or_equal`
 /// - `first_greater_than`
 /// - `first_greater_or_equal`
-#[derive(Clone, Debug)]
+///
+/// Its `Debug` output is written in terms of these methods, e.g. `first_greater_than(b"abc") + 2`.
+#[derive(Clone)]
 pub struct KeySelector<'a> {
     key: Bytes<'a>,
     or_equal: bool,
@@ -103,3 +107,125 @@ impl<'a> KeySelector<'a> {
         self.offset = 1;
     }
 }
+
+/// Moves the selector `offset` keys further, keeping its anchor key and `or_equal` flag.
+///
+/// `KeySelector::first_greater_or_equal(key) + 2` picks the second key after the first key
+/// greater than or equal to `key`.
+///
+/// # Panics
+///
+/// Panics if the resulting offset does not fit in an `i32`, in release builds too.
+impl<'a> Add<i32> for KeySelector<'a> {
+    type Output = Self;
+
+    fn add(mut self, offset: i32) -> Self {
+        self.offset = self
+            .offset
+            .checked_add(offset)
+            .expect("key selector offset overflow");
+        self
+    }
+}
+
+/// Moves the selector `offset` keys backward, keeping its anchor key and `or_equal` flag.
+///
+/// # Panics
+///
+/// Panics if the resulting offset does not fit in an `i32`, in release builds too.
+impl<'a> Sub<i32> for KeySelector<'a> {
+    type Output = Self;
+
+    fn sub(mut self, offset: i32) -> Self {
+        self.offset = self
+            .offset
+            .checked_sub(offset)
+            .expect("key selector offset overflow");
+        self
+    }
+}
+
+/// Renders the selector as the canonical constructor it derives from, followed by the extra
+/// offset if any: `first_greater_or_equal(b"abc") + 2`, `last_less_than(b"xyz") - 1`.
+impl<'a> fmt::Debug for KeySelector<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // selectors with a positive offset are written as a first_greater_*, the others as a
+        // last_less_*
+        let (name, base) = match (self.or_equal, self.offset > 0) {
+            (false, false) => ("last_less_than", 0),
+            (true, false) => ("last_less_or_equal", 0),
+            (true, true) => ("first_greater_than", 1),
+            (false, true) => ("first_greater_or_equal", 1),
+        };
+        write!(f, "{}({:?})", name, self.key)?;
+        match i64::from(self.offset) - base {
+            0 => Ok(()),
+            extra if extra > 0 => write!(f, " + {}", extra),
+            extra => write!(f, " - {}", -extra),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debug() {
+        let debug = |selector: KeySelector| format!("{:?}", selector);
+        assert_eq!(
+            debug(KeySelector::first_greater_than(&b"abc"[..])),
+            r#"first_greater_than(b"abc")"#
+        );
+        assert_eq!(
+            debug(KeySelector::first_greater_or_equal(&b"abc"[..])),
+            r#"first_greater_or_equal(b"abc")"#
+        );
+        assert_eq!(
+            debug(KeySelector::last_less_than(&b"xyz"[..])),
+            r#"last_less_than(b"xyz")"#
+        );
+        assert_eq!(
+            debug(KeySelector::last_less_or_equal(&b"xyz"[..])),
+            r#"last_less_or_equal(b"xyz")"#
+        );
+
+        assert_eq!(
+            debug(KeySelector::first_greater_than(&b"abc"[..]) + 2),
+            r#"first_greater_than(b"abc") + 2"#
+        );
+        assert_eq!(
+            debug(KeySelector::last_less_than(&b"a\x00"[..]) - 3),
+            r#"last_less_than(b"a\x00") - 3"#
+        );
+        // offsets crossing zero switch to the other family of constructors
+        assert_eq!(
+            debug(KeySelector::first_greater_or_equal(&b"k"[..]) - 1),
+            r#"last_less_than(b"k")"#
+        );
+        assert_eq!(
+            debug(KeySelector::new(Cow::Borrowed(b"k"), true, i32::MIN)),
+            r#"last_less_or_equal(b"k") - 2147483648"#
+        );
+    }
+
+    #[test]
+    fn test_offset_arithmetic() {
+        let selector = KeySelector::first_greater_or_equal(&b"k"[..]);
+        assert_eq!((selector.clone() + (i32::MAX - 1)).offset(), i32::MAX);
+        assert_eq!((selector.clone() - i32::MAX).offset(), 1 - i32::MAX);
+        assert_eq!((selector + 2 - 2).offset(), 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "key selector offset overflow")]
+    fn test_offset_overflow() {
+        let _ = KeySelector::first_greater_or_equal(&b"k"[..]) + i32::MAX;
+    }
+
+    #[test]
+    #[should_panic(expected = "key selector offset overflow")]
+    fn test_offset_underflow() {
+        let _ = KeySelector::last_less_than(&b"k"[..]) - i32::MAX - 2;
+    }
+}
@@ -10,8 +10,18 @@
 extern crate static_assertions;
 
 pub mod api;
+mod backoff;
+#[cfg(any(
+    feature = "fdb-6_2",
+    feature = "fdb-6_3",
+    feature = "fdb-7_0",
+    feature = "fdb-7_1",
+    feature = "fdb-7_3"
+))]
+mod batch_writer;
 #[cfg(any(feature = "fdb-5_1", feature = "fdb-5_2", feature = "fdb-6_0"))]
 pub mod cluster;
+mod counter;
 mod database;
 pub mod directory;
 mod error;
@@ -32,12 +42,24 @@ pub mod options;
     feature = "tenant-experimental"
 ))]
 pub mod tenant;
+mod timer;
+mod trace;
 mod transaction;
 pub mod tuple;
 
 #[cfg(any(feature = "fdb-5_1", feature = "fdb-5_2", feature = "fdb-6_0"))]
 pub use crate::cluster::Cluster;
 
+pub use crate::backoff::Backoff;
+#[cfg(any(
+    feature = "fdb-6_2",
+    feature = "fdb-6_3",
+    feature = "fdb-7_0",
+    feature = "fdb-7_1",
+    feature = "fdb-7_3"
+))]
+pub use crate::batch_writer::BatchWriter;
+pub use crate::counter::Counter;
 pub use crate::database::*;
 pub use crate::error::FdbBindingError;
 pub use crate::error::FdbError;
@@ -100,6 +122,20 @@ pub fn default_config_path() -> &'static str {
     "C:/ProgramData/foundationdb/fdb.cluster"
 }
 
+/// Returns the first key that sorts after every key starting with `prefix`.
+///
+/// Trailing `0xff` bytes are stripped and the last remaining byte is incremented. Returns `None`
+/// if there is no such key, i.e. if `prefix` is empty or only made of `0xff` bytes: every key
+/// up to the end of the keyspace then starts with `prefix`.
+pub fn strinc(prefix: &[u8]) -> Option<Vec<u8>> {
+    let end = directory::strinc(prefix.to_vec());
+    if end.is_empty() {
+        None
+    } else {
+        Some(end)
+    }
+}
+
 /// slice::from_raw_parts assumes the pointer to be aligned and non-null.
 /// Since Rust nightly (mid-February 2024), it is enforced with debug asserts,
 /// but the FDBServer can return a null pointer if the slice is empty:
@@ -15,7 +15,7 @@
 //! More info can be found in the [relevant documentation](https://github.com/apple/foundationdb/wiki/Everything-about-GetMappedRange).
 
 use crate::from_raw_fdb_slice;
-use crate::future::{FdbFutureHandle, FdbKeyValue};
+use crate::future::{FdbFutureHandle, FdbKeyValue, FdbValues};
 use crate::{error, KeySelector};
 use crate::{FdbError, FdbResult};
 use foundationdb_sys as fdb_sys;
@@ -26,8 +26,11 @@ use std::ops::Deref;
 use std::sync::Arc;
 
 /// An slice of mapped keyvalues owned by a foundationDB future produced by the `get_mapped` method.
+///
+/// It can also be built from the [`FdbValues`] of a plain range read, see
+/// [`MappedKeyValues::from`], so that code written against the mapped API can consume both.
 pub struct MappedKeyValues {
-    _f: FdbFutureHandle,
+    _f: MappedKeyValuesOwner,
     mapped_keyvalues: *const FdbMappedKeyValue,
     len: i32,
     more: bool,
@@ -42,6 +45,17 @@ impl MappedKeyValues {
     }
 }
 
+/// The memory the mapped keyvalues point into, only held to keep it alive.
+#[allow(dead_code)]
+enum MappedKeyValuesOwner {
+    Future(FdbFutureHandle),
+    // the descriptors point into the keyvalues of the plain range read
+    Unmapped(FdbValues, Vec<fdb_sys::FDBMappedKeyValue>),
+}
+// the descriptors are never written to once built
+unsafe impl Sync for MappedKeyValuesOwner {}
+unsafe impl Send for MappedKeyValuesOwner {}
+
 impl TryFrom<FdbFutureHandle> for MappedKeyValues {
     type Error = FdbError;
     fn try_from(f: FdbFutureHandle) -> FdbResult<Self> {
@@ -59,7 +73,7 @@ impl TryFrom<FdbFutureHandle> for MappedKeyValues {
         }
 
         Ok(MappedKeyValues {
-            _f: f,
+            _f: MappedKeyValuesOwner::Future(f),
             mapped_keyvalues: mapped_keyvalues as *const FdbMappedKeyValue,
             len,
             more: more != 0,
@@ -67,6 +81,61 @@ impl TryFrom<FdbFutureHandle> for MappedKeyValues {
     }
 }
 
+/// Wraps the result of a plain range read, each keyvalue becoming the parent key and value of a
+/// mapped keyvalue whose secondary range is empty.
+///
+/// ```no_run
+/// # async fn read(trx: &foundationdb::Transaction) -> foundationdb::FdbResult<()> {
+/// use foundationdb::mapped_key_values::MappedKeyValues;
+///
+/// let values = trx.get_range(&(b"a".as_ref(), b"b".as_ref()).into(), 1, false).await?;
+/// for mapped in &MappedKeyValues::from(values) {
+///     assert!(mapped.key_values().is_empty());
+/// }
+/// # Ok(())
+/// # }
+/// ```
+impl From<FdbValues> for MappedKeyValues {
+    fn from(values: FdbValues) -> Self {
+        let empty_selector = fdb_sys::FDBKeySelector {
+            key: fdb_sys::FDBKey {
+                key: std::ptr::null(),
+                key_length: 0,
+            },
+            orEqual: 0,
+            offset: 0,
+        };
+        let descriptors: Vec<fdb_sys::FDBMappedKeyValue> = values
+            .iter()
+            .map(|kv| fdb_sys::FDBMappedKeyValue {
+                key: fdb_sys::FDBKey {
+                    key: kv.key().as_ptr(),
+                    key_length: kv.key().len() as i32,
+                },
+                value: fdb_sys::FDBKey {
+                    key: kv.value().as_ptr(),
+                    key_length: kv.value().len() as i32,
+                },
+                getRange: fdb_sys::FDBGetRangeReqAndResult {
+                    begin: empty_selector,
+                    end: empty_selector,
+                    data: std::ptr::null_mut(),
+                    m_size: 0,
+                    m_capacity: 0,
+                },
+                buffer: [0; 32],
+            })
+            .collect();
+
+        MappedKeyValues {
+            mapped_keyvalues: descriptors.as_ptr() as *const FdbMappedKeyValue,
+            len: descriptors.len() as i32,
+            more: values.more(),
+            _f: MappedKeyValuesOwner::Unmapped(values, descriptors),
+        }
+    }
+}
+
 #[repr(packed)]
 /// A KeyValue produced by a mapped operation, ownder by a Foundation Future.
 pub struct FdbMappedKeyValue(fdb_sys::FDBMappedKeyValue);
@@ -161,7 +230,7 @@ impl<'a> IntoIterator for &'a MappedKeyValues {
 
 /// An FdbMappedValue that you can own.
 pub struct FdbMappedValue {
-    _f: Arc<FdbFutureHandle>,
+    _f: Arc<MappedKeyValuesOwner>,
     mapped_keyvalue: *const FdbMappedKeyValue,
 }
 
@@ -203,7 +272,7 @@ impl Eq for FdbMappedValue {}
 
 /// An iterator of mapped keyvalues owned by a foundationDB future
 pub struct FdbMappedValuesIter {
-    f: Arc<FdbFutureHandle>,
+    f: Arc<MappedKeyValuesOwner>,
     mapped_keyvalues: *const FdbMappedKeyValue,
     len: i32,
     pos: i32,
@@ -16,7 +16,7 @@ use crate::{
     RangeOption, RetryableTransaction, TransactOption, Transaction,
 };
 use foundationdb_sys as fdb_sys;
-use futures::TryStreamExt;
+use futures::{stream, Stream, TryStreamExt};
 use serde::{Deserialize, Serialize};
 use serde_json::Error;
 use std::ptr::NonNull;
@@ -440,4 +440,71 @@ impl TenantManagement {
             .try_collect::<Vec<Result<TenantInfo, serde_json::Error>>>()
             .await
     }
+
+    /// Lists all tenants of the cluster, reading at most `page_size` of them per transaction.
+    ///
+    /// Each page is read in its own retried transaction, starting right after the last tenant
+    /// of the previous page, so that listing thousands of tenants does not hit the transaction
+    /// time limit. The stream yields the name of each tenant along with its information, in
+    /// name order. Malformed tenant information is reported as a `FdbBindingError::CustomError`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `page_size` is zero.
+    pub fn list_tenants_paged(
+        db: &Database,
+        page_size: usize,
+    ) -> impl Stream<Item = Result<(String, TenantInfo), FdbBindingError>> + '_ {
+        assert!(page_size > 0, "page_size must not be zero");
+
+        stream::try_unfold(Some(TENANT_MAP_PREFIX.to_vec()), move |begin| async move {
+            let Some(begin) = begin else {
+                return Ok(None);
+            };
+            let begin_ref = &begin;
+
+            let (page, more) = db
+                .run(|trx, _maybe_committed| async move {
+                    trx.set_option(TransactionOption::ReadSystemKeys)?;
+                    trx.set_option(TransactionOption::ReadLockAware)?;
+
+                    let range_option = RangeOption {
+                        begin: KeySelector::first_greater_or_equal(begin_ref.as_slice()),
+                        end: KeySelector::first_greater_or_equal(TENANT_MAP_PREFIX_END),
+                        limit: Some(page_size),
+                        ..Default::default()
+                    };
+                    let values = trx.get_range(&range_option, 1, false).await?;
+                    let page: Vec<(Vec<u8>, Vec<u8>)> = values
+                        .iter()
+                        .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
+                        .collect();
+                    Ok((page, values.more()))
+                })
+                .await?;
+
+            // the next page starts with the first key following the last tenant
+            let next_begin = match page.last() {
+                Some((key, _)) if more => {
+                    let mut next_begin = key.clone();
+                    next_begin.push(0);
+                    Some(next_begin)
+                }
+                _ => None,
+            };
+
+            let tenants: Vec<Result<(String, TenantInfo), FdbBindingError>> = page
+                .into_iter()
+                .map(|(key, value)| {
+                    let tenant_info = TenantInfo::try_from((key.as_slice(), value.as_slice()))
+                        .map_err(|err| FdbBindingError::new_custom_error(Box::new(err)))?;
+                    let name = String::from_utf8_lossy(&key[TENANT_MAP_PREFIX.len()..]);
+                    Ok((name.into_owned(), tenant_info))
+                })
+                .collect();
+
+            Ok::<_, FdbBindingError>(Some((stream::iter(tenants), next_begin)))
+        })
+        .try_flatten()
+    }
 }
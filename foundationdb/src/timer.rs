@@ -0,0 +1,188 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! A runtime agnostic timer.
+//!
+//! The crate does not depend on any async runtime, so delays are served by a single timer thread,
+//! started on first use, that completes a oneshot channel once each deadline is reached.
+
+use futures::channel::oneshot;
+use futures::{Future, FutureExt};
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::pin::Pin;
+use std::sync::{Condvar, Mutex, OnceLock};
+use std::task::{Context, Poll};
+use std::thread;
+use std::time::{Duration, Instant};
+
+struct Timer {
+    deadlines: Mutex<BinaryHeap<Reverse<Deadline>>>,
+    wakeup: Condvar,
+}
+
+struct Deadline {
+    at: Instant,
+    tx: oneshot::Sender<()>,
+}
+
+impl PartialEq for Deadline {
+    fn eq(&self, other: &Self) -> bool {
+        self.at == other.at
+    }
+}
+
+impl Eq for Deadline {}
+
+impl PartialOrd for Deadline {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Deadline {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.at.cmp(&other.at)
+    }
+}
+
+impl Timer {
+    fn get() -> &'static Timer {
+        static TIMER: OnceLock<Timer> = OnceLock::new();
+        static STARTED: OnceLock<()> = OnceLock::new();
+
+        let timer = TIMER.get_or_init(|| Timer {
+            deadlines: Mutex::new(BinaryHeap::new()),
+            wakeup: Condvar::new(),
+        });
+        STARTED.get_or_init(|| {
+            thread::Builder::new()
+                .name("foundationdb-timer".to_string())
+                .spawn(move || timer.run())
+                .expect("failed to spawn the timer thread");
+        });
+        timer
+    }
+
+    fn add(&self, deadline: Deadline) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+        let earliest = deadlines
+            .peek()
+            .map_or(true, |Reverse(d)| deadline.at < d.at);
+        deadlines.push(Reverse(deadline));
+        if earliest {
+            self.wakeup.notify_one();
+        }
+    }
+
+    fn run(&self) {
+        let mut deadlines = self.deadlines.lock().unwrap();
+        loop {
+            let now = Instant::now();
+            while deadlines.peek().is_some_and(|Reverse(d)| d.at <= now) {
+                let Reverse(deadline) = deadlines.pop().unwrap();
+                // the receiver may have been dropped already, there is nobody left to notify
+                let _ = deadline.tx.send(());
+            }
+            // forget the delays that were dropped before their deadline
+            if deadlines.iter().any(|Reverse(d)| d.tx.is_canceled()) {
+                deadlines.retain(|Reverse(d)| !d.tx.is_canceled());
+            }
+            deadlines = match deadlines.peek() {
+                Some(Reverse(d)) => {
+                    let timeout = d.at.saturating_duration_since(now);
+                    self.wakeup.wait_timeout(deadlines, timeout).unwrap().0
+                }
+                None => self.wakeup.wait(deadlines).unwrap(),
+            };
+        }
+    }
+}
+
+/// Returns a future that completes once `duration` is elapsed, starting from now.
+pub(crate) fn delay(duration: Duration) -> Delay {
+    // a deadline too far in the future to be represented is never reached
+    let rx = Instant::now().checked_add(duration).map(|at| {
+        let (tx, rx) = oneshot::channel();
+        Timer::get().add(Deadline { at, tx });
+        rx
+    });
+    Delay { rx }
+}
+
+/// Future returned by `delay`.
+///
+/// Dropping it before its deadline wakes the timer thread up, so that the deadline is forgotten
+/// right away instead of being kept until it expires.
+pub(crate) struct Delay {
+    rx: Option<oneshot::Receiver<()>>,
+}
+
+impl Future for Delay {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<()> {
+        match self.rx.as_mut() {
+            Some(rx) => {
+                futures::ready!(rx.poll_unpin(cx)).ok();
+                self.rx = None;
+                Poll::Ready(())
+            }
+            None => Poll::Pending,
+        }
+    }
+}
+
+impl Drop for Delay {
+    fn drop(&mut self) {
+        if let Some(rx) = self.rx.take() {
+            drop(rx);
+            let timer = Timer::get();
+            // taking the lock makes sure the timer thread is waiting, and thus gets notified
+            let _deadlines = timer.deadlines.lock().unwrap();
+            timer.wakeup.notify_one();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::stream::{FuturesUnordered, StreamExt};
+
+    #[test]
+    fn test_delays_complete_in_deadline_order() {
+        let start = Instant::now();
+        let delays: FuturesUnordered<_> = [30u64, 10, 20, 0]
+            .into_iter()
+            .map(|ms| delay(Duration::from_millis(ms)).map(move |()| ms))
+            .collect();
+        let order: Vec<u64> = futures::executor::block_on(delays.collect());
+        assert_eq!(order, vec![0, 10, 20, 30]);
+        assert!(start.elapsed() >= Duration::from_millis(30));
+    }
+
+    #[test]
+    fn test_dropped_delay() {
+        let far = Duration::from_secs(3600);
+        drop(delay(far));
+        futures::executor::block_on(delay(Duration::from_millis(10)));
+
+        // the dropped deadline does not stay in the heap until it expires
+        let deadlines = Timer::get().deadlines.lock().unwrap();
+        let horizon = Instant::now() + far / 2;
+        assert!(deadlines.iter().all(|Reverse(d)| d.at < horizon));
+    }
+
+    #[test]
+    fn test_unreachable_delay() {
+        let mut unreachable = delay(Duration::MAX);
+        let waker = futures::task::noop_waker();
+        let mut cx = Context::from_waker(&waker);
+        assert!(Pin::new(&mut unreachable).poll(&mut cx).is_pending());
+    }
+}
@@ -0,0 +1,130 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+//! Spans around the transaction lifecycle, emitted through `tracing` when the `tracing` feature
+//! is enabled.
+//!
+//! All spans are at the `DEBUG` level:
+//! * `fdb.create_trx`,
+//! * `fdb.attempt`, around each run of a `Database::run` closure, with its `retry_count`,
+//! * `fdb.commit`, around each commit attempt,
+//! * `fdb.on_error`, around the retry backoff, with the `error_code` and the `delay_ms` spent,
+//! * `fdb.get_range`, around each range read, with the length of its begin and end keys, its
+//!   `iteration` and the `count` of key-value pairs read,
+//! * `fdb.get_read_version`, with the `read_version` obtained.
+//!
+//! Without the feature, the helpers below return the futures they are given untouched.
+
+#[cfg(feature = "tracing")]
+mod spans {
+    use std::time::Instant;
+
+    use futures::{Future, FutureExt};
+    use tracing::field::Empty;
+    use tracing::Instrument;
+
+    use crate::future::FdbValues;
+    use crate::FdbResult;
+
+    pub(crate) fn create_trx<T>(f: impl FnOnce() -> T) -> T {
+        tracing::debug_span!("fdb.create_trx").in_scope(f)
+    }
+
+    pub(crate) fn attempt<F: Future>(f: F, retry_count: u32) -> impl Future<Output = F::Output> {
+        f.instrument(tracing::debug_span!("fdb.attempt", retry_count))
+    }
+
+    pub(crate) fn commit<F: Future>(f: F) -> impl Future<Output = F::Output> {
+        f.instrument(tracing::debug_span!("fdb.commit"))
+    }
+
+    pub(crate) fn on_error<F: Future>(f: F, error_code: i32) -> impl Future<Output = F::Output> {
+        let span = tracing::debug_span!("fdb.on_error", error_code, delay_ms = Empty);
+        let recorded = span.clone();
+        let start = Instant::now();
+        f.map(move |output| {
+            recorded.record("delay_ms", start.elapsed().as_millis() as u64);
+            output
+        })
+        .instrument(span)
+    }
+
+    pub(crate) fn get_range<F>(
+        f: F,
+        begin_key_len: usize,
+        end_key_len: usize,
+        iteration: usize,
+    ) -> impl Future<Output = FdbResult<FdbValues>>
+    where
+        F: Future<Output = FdbResult<FdbValues>>,
+    {
+        let span = tracing::debug_span!(
+            "fdb.get_range",
+            begin_key_len,
+            end_key_len,
+            iteration,
+            count = Empty
+        );
+        let recorded = span.clone();
+        f.map(move |values| {
+            if let Ok(values) = &values {
+                recorded.record("count", values.len());
+            }
+            values
+        })
+        .instrument(span)
+    }
+
+    pub(crate) fn get_read_version<F>(f: F) -> impl Future<Output = FdbResult<i64>>
+    where
+        F: Future<Output = FdbResult<i64>>,
+    {
+        let span = tracing::debug_span!("fdb.get_read_version", read_version = Empty);
+        let recorded = span.clone();
+        f.map(move |version| {
+            if let Ok(version) = version {
+                recorded.record("read_version", version);
+            }
+            version
+        })
+        .instrument(span)
+    }
+}
+
+#[cfg(not(feature = "tracing"))]
+mod spans {
+    pub(crate) fn create_trx<T>(f: impl FnOnce() -> T) -> T {
+        f()
+    }
+
+    pub(crate) fn attempt<F>(f: F, _retry_count: u32) -> F {
+        f
+    }
+
+    pub(crate) fn commit<F>(f: F) -> F {
+        f
+    }
+
+    pub(crate) fn on_error<F>(f: F, _error_code: i32) -> F {
+        f
+    }
+
+    pub(crate) fn get_range<F>(
+        f: F,
+        _begin_key_len: usize,
+        _end_key_len: usize,
+        _iteration: usize,
+    ) -> F {
+        f
+    }
+
+    pub(crate) fn get_read_version<F>(f: F) -> F {
+        f
+    }
+}
+
+pub(crate) use spans::*;
@@ -12,13 +12,17 @@
 
 use foundationdb_sys as fdb_sys;
 use std::fmt;
-use std::ops::{Deref, Range, RangeInclusive};
+use std::ops::{ControlFlow, Deref, Range, RangeInclusive};
 use std::ptr::NonNull;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::future::*;
 use crate::keyselector::*;
 use crate::options;
+use crate::timer;
+use crate::trace;
+use crate::tuple::{pack, unpack, Bytes, PackError, PackResult, TupleUnpack};
 
 use crate::{error, FdbError, FdbResult};
 use foundationdb_macros::cfg_api_versions;
@@ -26,7 +30,8 @@ use foundationdb_macros::cfg_api_versions;
 use crate::error::FdbBindingError;
 
 use futures::{
-    future, future::Either, stream, Future, FutureExt, Stream, TryFutureExt, TryStreamExt,
+    future, future::Either, stream, AsyncWrite, AsyncWriteExt, Future, FutureExt, Stream,
+    TryFutureExt, TryStreamExt,
 };
 
 #[cfg_api_versions(min = 610)]
@@ -93,10 +98,12 @@ impl TransactionCommitError {
     /// You should not call this method most of the times and use `Database::transact` which
     /// implements a retry loop strategy for you.
     pub fn on_error(self) -> impl Future<Output = FdbResult<Transaction>> {
-        FdbFuture::<()>::new(unsafe {
-            fdb_sys::fdb_transaction_on_error(self.tr.inner.as_ptr(), self.err.code())
+        let code = self.err.code();
+        let on_error = FdbFuture::<()>::new(unsafe {
+            fdb_sys::fdb_transaction_on_error(self.tr.inner.as_ptr(), code)
         })
-        .map_ok(|()| self.tr)
+        .map_ok(|()| self.tr);
+        trace::on_error(on_error, code)
     }
 
     /// Reset the transaction to its initial state.
@@ -106,6 +113,21 @@ impl TransactionCommitError {
         self.tr.reset();
         self.tr
     }
+
+    /// Splits the error into the commit error and the transaction that failed to commit, which
+    /// still holds its reads and writes.
+    ///
+    /// The transaction can then be retried by hand with `Transaction::on_error`.
+    pub fn into_parts(self) -> (FdbError, Transaction) {
+        (self.err, self.tr)
+    }
+
+    /// Returns the read conflict ranges that made the commit fail, see
+    /// [Transaction::get_conflicting_keys].
+    #[cfg_api_versions(min = 630)]
+    pub async fn get_conflicting_keys(&self) -> FdbResult<Vec<Range<Vec<u8>>>> {
+        self.tr.get_conflicting_keys().await
+    }
 }
 
 impl Deref for TransactionCommitError {
@@ -171,6 +193,8 @@ pub struct Transaction {
     // Order of fields should not be changed, because Rust drops field top-to-bottom, and
     // transaction should be dropped before cluster.
     inner: NonNull<fdb_sys::FDBTransaction>,
+    // options set through `set_option`, as FDB cannot read them back
+    options: Mutex<Vec<(options::TransactionOption, Vec<u8>)>>,
 }
 unsafe impl Send for Transaction {}
 unsafe impl Sync for Transaction {}
@@ -241,11 +265,27 @@ pub struct RangeOption<'a> {
     /// If true, key-value pairs will be returned in reverse lexicographical order beginning at
     /// the end of the range.
     pub reverse: bool,
+    /// If set, the budget of bytes to read across all the pages of a scan, counting both keys and
+    /// values like `target_bytes` does.
+    ///
+    /// Each page asks for at most the budget left as its `target_bytes`, and `next_range` stops
+    /// once the keys and values read reach the budget. As `target_bytes` is a soft cap, the last
+    /// page may exceed it.
+    pub byte_limit: Option<usize>,
     #[doc(hidden)]
     pub __non_exhaustive: std::marker::PhantomData<()>,
 }
 
 impl<'a> RangeOption<'a> {
+    /// The `target_bytes` of the next page, lowered to the `byte_limit` left if any.
+    fn page_target_bytes(&self) -> usize {
+        match (self.target_bytes, self.byte_limit) {
+            (target_bytes, None) => target_bytes,
+            (0, Some(byte_limit)) => byte_limit,
+            (target_bytes, Some(byte_limit)) => target_bytes.min(byte_limit),
+        }
+    }
+
     /// Reverses the range direction.
     pub fn rev(mut self) -> Self {
         self.reverse = !self.reverse;
@@ -267,6 +307,14 @@ impl<'a> RangeOption<'a> {
             }
         }
 
+        if let Some(byte_limit) = self.byte_limit.as_mut() {
+            let read: usize = kvs.iter().map(|kv| kv.key().len() + kv.value().len()).sum();
+            *byte_limit = byte_limit.saturating_sub(read);
+            if *byte_limit == 0 {
+                return None;
+            }
+        }
+
         if self.reverse {
             self.end.make_first_greater_or_equal(last_key);
         } else {
@@ -291,6 +339,17 @@ impl<'a> RangeOption<'a> {
             }
         }
 
+        if let Some(byte_limit) = self.byte_limit.as_mut() {
+            let read: usize = kvs
+                .iter()
+                .map(|kv| kv.parent_key().len() + kv.parent_value().len())
+                .sum();
+            *byte_limit = byte_limit.saturating_sub(read);
+            if *byte_limit == 0 {
+                return None;
+            }
+        }
+
         if self.reverse {
             self.end.make_first_greater_or_equal(last_key);
         } else {
@@ -309,6 +368,7 @@ impl<'a> Default for RangeOption<'a> {
             target_bytes: 0,
             mode: options::StreamingMode::Iterator,
             reverse: false,
+            byte_limit: None,
             __non_exhaustive: std::marker::PhantomData,
         }
     }
@@ -373,14 +433,318 @@ impl From<std::ops::RangeInclusive<std::vec::Vec<u8>>> for RangeOption<'static>
     }
 }
 
+/// Where a paginated range scan stopped, to resume it from another transaction.
+///
+/// A cursor holds the part of the range left to read, its direction and, if the scan was
+/// limited, the number of key-value pairs left to return. It can be serialized with `to_bytes`,
+/// for example to hand a continuation token to a client, and read back with `from_bytes`.
+///
+/// Pages are read with [Transaction::get_range_from_cursor], which returns the cursor of the
+/// next page until the range is exhausted.
+#[derive(Debug, Clone)]
+pub struct RangeCursor {
+    begin: KeySelector<'static>,
+    end: KeySelector<'static>,
+    limit: Option<usize>,
+    reverse: bool,
+}
+
+impl RangeCursor {
+    const VERSION: u32 = 1;
+
+    /// Serializes the cursor, as a tuple.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        fn selector<'a>(selector: &'a KeySelector) -> (Bytes<'a>, bool, i32) {
+            (
+                Bytes::from(selector.key()),
+                selector.or_equal(),
+                selector.offset(),
+            )
+        }
+        pack(&(
+            Self::VERSION,
+            selector(&self.begin),
+            selector(&self.end),
+            self.reverse,
+            self.limit.unwrap_or(0) as u64,
+        ))
+    }
+
+    /// Reads back a cursor serialized by `to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> PackResult<Self> {
+        type Selector<'a> = (Bytes<'a>, bool, i32);
+        let (version, begin, end, reverse, limit): (u32, Selector, Selector, bool, u64) =
+            unpack(bytes)?;
+        if version != Self::VERSION {
+            return Err(PackError::Message(
+                format!("unsupported range cursor version {}", version).into(),
+            ));
+        }
+        let selector = |(key, or_equal, offset): Selector| {
+            KeySelector::new(key.into_owned().into(), or_equal, offset)
+        };
+        Ok(Self {
+            begin: selector(begin),
+            end: selector(end),
+            reverse,
+            limit: usize::try_from(limit).ok().filter(|&limit| limit != 0),
+        })
+    }
+
+    fn range_option(&self) -> RangeOption<'static> {
+        RangeOption {
+            begin: self.begin.clone(),
+            end: self.end.clone(),
+            limit: self.limit,
+            reverse: self.reverse,
+            ..RangeOption::default()
+        }
+    }
+}
+
+/// Starts a scan of the given range, keeping its bounds, direction and limit.
+impl<'a> From<RangeOption<'a>> for RangeCursor {
+    fn from(opt: RangeOption<'a>) -> Self {
+        let owned = |selector: KeySelector| {
+            KeySelector::new(
+                selector.key().to_vec().into(),
+                selector.or_equal(),
+                selector.offset(),
+            )
+        };
+        Self {
+            begin: owned(opt.begin),
+            end: owned(opt.end),
+            limit: opt.limit.filter(|&limit| limit != 0),
+            reverse: opt.reverse,
+        }
+    }
+}
+
+/// A typed set of the most common transaction options.
+///
+/// Durations are converted to the milliseconds FoundationDB expects, saturating at
+/// `i32::MAX`. Options are only set once `TransactionOptions::apply` is called.
+///
+/// ```no_run
+/// use foundationdb::{Database, TransactionOptions};
+/// use std::time::Duration;
+///
+/// # fn example(db: &Database) -> foundationdb::FdbResult<()> {
+/// let trx = db.create_trx()?;
+/// TransactionOptions::new()
+///     .timeout(Duration::from_secs(5))
+///     .retry_limit(10)
+///     .apply(&trx)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct TransactionOptions {
+    options: Vec<options::TransactionOption>,
+}
+
+fn duration_to_millis(duration: Duration) -> i32 {
+    duration.as_millis().try_into().unwrap_or(i32::MAX)
+}
+
+impl TransactionOptions {
+    /// Creates an empty set of options.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Cancels the transaction, and all its retries, once `timeout` is elapsed. The transaction
+    /// then fails with `transaction_timed_out` (1031). A zero duration disables all timeouts.
+    pub fn timeout(self, timeout: Duration) -> Self {
+        self.option(options::TransactionOption::Timeout(duration_to_millis(
+            timeout,
+        )))
+    }
+
+    /// Limits the number of retries done through `on_error`. `-1` disables the limit.
+    pub fn retry_limit(self, limit: i32) -> Self {
+        self.option(options::TransactionOption::RetryLimit(limit))
+    }
+
+    /// Caps the delay added by `on_error` between two retries.
+    pub fn max_retry_delay(self, delay: Duration) -> Self {
+        self.option(options::TransactionOption::MaxRetryDelay(
+            duration_to_millis(delay),
+        ))
+    }
+
+    /// Runs the transaction at batch priority, below any other work.
+    pub fn priority_batch(self) -> Self {
+        self.option(options::TransactionOption::PriorityBatch)
+    }
+
+    /// Reads are not affected by the writes of the transaction itself.
+    pub fn read_your_writes_disable(self) -> Self {
+        self.option(options::TransactionOption::ReadYourWritesDisable)
+    }
+
+    /// Adds an option that has no typed method.
+    pub fn option(mut self, option: options::TransactionOption) -> Self {
+        self.options.push(option);
+        self
+    }
+
+    /// Sets all the options on the given transaction, in the order they were added.
+    pub fn apply(&self, trx: &Transaction) -> FdbResult<()> {
+        for option in &self.options {
+            trx.set_option(option.clone())?;
+        }
+        Ok(())
+    }
+}
+
+/// The encoding used by [Transaction::stream_range_to] to write key-value pairs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Each pair is written as the key length (big-endian `u32`), the key, the value length
+    /// (big-endian `u32`) and the value.
+    LengthPrefixed,
+    /// Each pair is written as a JSON object on its own line, `{"key":"..","value":".."}`, with
+    /// the key and the value hex encoded.
+    JsonLines,
+}
+
+impl ExportFormat {
+    fn encode_into(self, key: &[u8], value: &[u8], out: &mut Vec<u8>) {
+        match self {
+            ExportFormat::LengthPrefixed => {
+                out.extend_from_slice(&(key.len() as u32).to_be_bytes());
+                out.extend_from_slice(key);
+                out.extend_from_slice(&(value.len() as u32).to_be_bytes());
+                out.extend_from_slice(value);
+            }
+            ExportFormat::JsonLines => {
+                out.extend_from_slice(b"{\"key\":\"");
+                hex_encode_into(key, out);
+                out.extend_from_slice(b"\",\"value\":\"");
+                hex_encode_into(value, out);
+                out.extend_from_slice(b"\"}\n");
+            }
+        }
+    }
+}
+
+/// Decodes the values read by [Transaction::get_range_decode].
+///
+/// It is implemented for closures, `|value: &[u8]| -> Result<V, E>`, so that any serialization
+/// format can be plugged in.
+pub trait ValueDecoder<V> {
+    /// The error returned when a value cannot be decoded.
+    type Error: std::error::Error + Send + Sync + 'static;
+
+    /// Decodes a value.
+    fn decode(&self, value: &[u8]) -> Result<V, Self::Error>;
+}
+
+impl<V, E, F> ValueDecoder<V> for F
+where
+    F: Fn(&[u8]) -> Result<V, E>,
+    E: std::error::Error + Send + Sync + 'static,
+{
+    type Error = E;
+
+    fn decode(&self, value: &[u8]) -> Result<V, E> {
+        self(value)
+    }
+}
+
+/// A value that a [ValueDecoder] failed to decode, returned by
+/// [Transaction::get_range_decode] as a `FdbBindingError::CustomError`.
+#[derive(Debug)]
+pub struct ValueDecodeError {
+    key: Vec<u8>,
+    source: Box<dyn std::error::Error + Send + Sync>,
+}
+
+impl ValueDecodeError {
+    /// The key of the value that could not be decoded.
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+}
+
+impl fmt::Display for ValueDecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "failed to decode the value of {}: {}",
+            Bytes::from(self.key.as_slice()),
+            self.source
+        )
+    }
+}
+
+impl std::error::Error for ValueDecodeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(self.source.as_ref())
+    }
+}
+
+fn hex_encode_into(bytes: &[u8], out: &mut Vec<u8>) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    for &byte in bytes {
+        out.push(HEX[(byte >> 4) as usize]);
+        out.push(HEX[(byte & 0xf) as usize]);
+    }
+}
+
+/// Whether a packed mapper tuple references the parent key-value through at least one `{K[n]}`
+/// or `{V[n]}` placeholder.
+#[cfg_api_versions(min = 710)]
+fn mapper_has_placeholder(mapper: &[u8]) -> bool {
+    mapper.windows(3).enumerate().any(|(i, w)| {
+        if w != b"{K[" && w != b"{V[" {
+            return false;
+        }
+        let rest = &mapper[i + 3..];
+        let digits = rest.iter().take_while(|b| b.is_ascii_digit()).count();
+        digits > 0 && rest[digits..].starts_with(b"]}")
+    })
+}
+
+/// Whether no two non-empty `[begin, end)` ranges share a key.
+fn ranges_are_disjoint(ranges: &[(&[u8], &[u8])]) -> bool {
+    let mut sorted: Vec<_> = ranges.iter().filter(|(begin, end)| begin < end).collect();
+    sorted.sort_unstable();
+    sorted.windows(2).all(|w| w[0].1 <= w[1].0)
+}
+
 impl Transaction {
     pub(crate) fn new(inner: NonNull<fdb_sys::FDBTransaction>) -> Self {
-        Self { inner }
+        Self {
+            inner,
+            options: Mutex::new(Vec::new()),
+        }
     }
 
     /// Called to set an option on an FDBTransaction.
+    ///
+    /// Options set successfully are recorded, see `options_set`.
     pub fn set_option(&self, opt: options::TransactionOption) -> FdbResult<()> {
-        unsafe { opt.apply(self.inner.as_ptr()) }
+        unsafe { opt.apply(self.inner.as_ptr()) }?;
+        let param = opt.param();
+        self.options.lock().unwrap().push((opt, param));
+        Ok(())
+    }
+
+    /// Returns the options set on this transaction with `set_option`, in the order they were set,
+    /// along with their parameter as passed to the C API.
+    ///
+    /// FDB does not expose the options of a transaction, this is a record kept by the binding,
+    /// meant for debugging: options set with `set_raw_option` are not part of it. The record is
+    /// cleared by `reset` and left untouched by `on_error`, see the documentation of each option
+    /// for whether FDB keeps it across a retry.
+    ///
+    /// The record is returned as a copy rather than a `&[...]`: `set_option` only borrows the
+    /// transaction, so the record sits behind a lock that a returned slice could not outlive.
+    pub fn options_set(&self) -> Vec<(options::TransactionOption, Vec<u8>)> {
+        self.options.lock().unwrap().clone()
     }
 
     /// Pass through an option given a code and raw data. Useful when creating a passthrough layer
@@ -411,6 +775,21 @@ impl Transaction {
         }
     }
 
+    /// Allows the transaction to read and write while the database is locked, during a restore
+    /// for example.
+    ///
+    /// Shorthand for setting `TransactionOption::LockAware`.
+    pub fn set_lock_aware(&self) -> FdbResult<()> {
+        self.set_option(options::TransactionOption::LockAware)
+    }
+
+    /// Allows the transaction to read, but not write, while the database is locked.
+    ///
+    /// Shorthand for setting `TransactionOption::ReadLockAware`.
+    pub fn set_read_lock_aware(&self) -> FdbResult<()> {
+        self.set_option(options::TransactionOption::ReadLockAware)
+    }
+
     /// Modify the database snapshot represented by transaction to change the given
     /// key to have the given value.
     ///
@@ -434,6 +813,27 @@ impl Transaction {
         }
     }
 
+    /// Sets each of the given key-value pairs, as `set` does, in iteration order.
+    ///
+    /// ```no_run
+    /// # fn example(trx: &foundationdb::Transaction) {
+    /// use std::collections::HashMap;
+    ///
+    /// let fixtures = HashMap::from([("a", "1"), ("b", "2")]);
+    /// trx.set_all(&fixtures);
+    /// # }
+    /// ```
+    pub fn set_all<I, K, V>(&self, kvs: I)
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        for (key, value) in kvs {
+            self.set(key.as_ref(), value.as_ref());
+        }
+    }
+
     /// Modify the database snapshot represented by transaction to remove the given key from the
     /// database.
     ///
@@ -454,6 +854,27 @@ impl Transaction {
         }
     }
 
+    /// Moves the value of `old` to `new`, within this transaction.
+    ///
+    /// `old` is read, and if it is present, `new` is set to its value and `old` is cleared.
+    /// Nothing is committed: the rename takes effect with the rest of the transaction.
+    ///
+    /// Returns whether `old` was present. If it was not, `new` is left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `old` - the key to move the value from
+    /// * `new` - the key to move the value to
+    pub async fn rename_key(&self, old: &[u8], new: &[u8]) -> FdbResult<bool> {
+        let Some(value) = self.get(old, false).await? else {
+            return Ok(false);
+        };
+        // cleared first, so that renaming a key to itself keeps it
+        self.clear(old);
+        self.set(new, &value);
+        Ok(true)
+    }
+
     /// Reads a value from the database snapshot represented by transaction.
     ///
     /// Returns an FDBFuture which will be set to the value of key in the database if there is any.
@@ -477,6 +898,116 @@ impl Transaction {
         })
     }
 
+    /// Reads a value from the database snapshot represented by transaction, ignoring the writes
+    /// made so far by this transaction.
+    ///
+    /// This returns the value `key` had before the transaction, even once a write to `key` was
+    /// staged. It is a snapshot read issued between the `SnapshotRywDisable` and
+    /// `SnapshotRywEnable` options: those maintain a counter, so that the other snapshot reads of
+    /// the transaction keep seeing its writes, or not, as configured before the call. Unless
+    /// `snapshot` is set, `key` is then added to the read conflict ranges, as a regular read
+    /// would do.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the name of the key to be looked up in the database
+    /// * `snapshot` - `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_committed(
+        &self,
+        key: &[u8],
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<Option<FdbSlice>>> + Send + Sync + Unpin {
+        let read = || {
+            // applied without being recorded in `options_set`, they cancel out
+            unsafe { options::TransactionOption::SnapshotRywDisable.apply(self.inner.as_ptr()) }?;
+            let value = self.get(key, true);
+            unsafe { options::TransactionOption::SnapshotRywEnable.apply(self.inner.as_ptr()) }?;
+            if !snapshot {
+                let end = [key, b"\x00"].concat();
+                self.add_conflict_range(key, &end, options::ConflictRangeType::Read)?;
+            }
+            Ok(value)
+        };
+        match read() {
+            Ok(value) => Either::Left(value),
+            Err(err) => Either::Right(future::err(err)),
+        }
+    }
+
+    /// Reads a value from the database snapshot represented by transaction, falling back to
+    /// `default` if the key is not present.
+    ///
+    /// The returned value is copied out of the future, either from the database or from
+    /// `default`.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the name of the key to be looked up in the database
+    /// * `default` - the value returned when the key is missing
+    /// * `snapshot` - `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_or<'a>(
+        &self,
+        key: &[u8],
+        default: &'a [u8],
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<Vec<u8>>> + Send + Sync + Unpin + 'a {
+        self.get_or_else(key, snapshot, move || default.to_vec())
+    }
+
+    /// Reads a value from the database snapshot represented by transaction, computing a
+    /// fallback with `default` if the key is not present.
+    ///
+    /// `default` is only called when the key is missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - the name of the key to be looked up in the database
+    /// * `snapshot` - `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    /// * `default` - computes the value returned when the key is missing
+    pub fn get_or_else<F>(
+        &self,
+        key: &[u8],
+        snapshot: bool,
+        default: F,
+    ) -> impl Future<Output = FdbResult<Vec<u8>>> + Send + Sync + Unpin
+    where
+        F: FnOnce() -> Vec<u8> + Send + Sync + Unpin,
+    {
+        self.get(key, snapshot).map(move |result| {
+            result.map(|value| match value {
+                Some(value) => value.to_vec(),
+                None => default(),
+            })
+        })
+    }
+
+    /// Returns a view of this transaction whose reads are all
+    /// [snapshot reads](https://apple.github.io/foundationdb/api-c.html#snapshots).
+    ///
+    /// This is the equivalent of passing `snapshot = true` to every read method. The view only
+    /// exposes reads, writes should be done on the transaction itself.
+    pub fn snapshot(&self) -> SnapshotTransaction<'_> {
+        SnapshotTransaction { trx: self }
+    }
+
+    /// Reads the values of several keys from the database snapshot represented by transaction.
+    ///
+    /// All the reads are issued right away, so they are pipelined instead of waiting for each
+    /// other. The returned future resolves to the values positionally: the n-th element is the
+    /// value of the n-th key, `None` if there is no such key.
+    ///
+    /// # Arguments
+    ///
+    /// * `keys` - the names of the keys to be looked up in the database
+    /// * `snapshot` - `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_multi<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a [u8]>,
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<Vec<Option<FdbSlice>>>> + Send + Sync + Unpin {
+        future::try_join_all(keys.into_iter().map(|key| self.get(key, snapshot)))
+    }
+
     /// Modify the database snapshot represented by transaction to perform the operation indicated
     /// by operationType with operand param to the value stored by the given key.
     ///
@@ -501,6 +1032,11 @@ impl Transaction {
     /// If a transaction uses both an atomic operation and a strictly serializable read on the same
     /// key, the benefits of using the atomic operation (for both conflict checking and performance)
     /// are lost.
+    ///
+    /// Arithmetic and bitwise operations (`Add`, `Min`, `Max`, `BitAnd`, `BitOr`, `BitXor`...)
+    /// read both `param` and the existing value as little-endian integers, and no check is made
+    /// on their lengths: the existing value is extended or truncated to the length of `param`.
+    /// Prefer [`Transaction::add_i64`] to maintain 64-bit counters.
     pub fn atomic_op(&self, key: &[u8], param: &[u8], op_type: options::MutationType) {
         unsafe {
             fdb_sys::fdb_transaction_atomic_op(
@@ -514,6 +1050,54 @@ impl Transaction {
         }
     }
 
+    /// Atomically adds `delta` to the little-endian 64-bit integer stored at `key`.
+    ///
+    /// A missing key is treated as zero. The operand is always encoded on exactly 8 bytes, so
+    /// the stored value can be read back with `i64::from_le_bytes`.
+    pub fn add_i64(&self, key: &[u8], delta: i64) {
+        self.atomic_op(key, &delta.to_le_bytes(), options::MutationType::Add)
+    }
+
+    /// Atomically sets the little-endian unsigned 64-bit integer stored at `key` to `value` if
+    /// `value` is greater, storing `value` if the key is missing.
+    pub fn atomic_max_u64(&self, key: &[u8], value: u64) {
+        self.atomic_op(key, &value.to_le_bytes(), options::MutationType::Max)
+    }
+
+    /// Atomically sets the little-endian unsigned 64-bit integer stored at `key` to `value` if
+    /// `value` is smaller, storing `value` if the key is missing.
+    pub fn atomic_min_u64(&self, key: &[u8], value: u64) {
+        self.atomic_op(key, &value.to_le_bytes(), options::MutationType::Min)
+    }
+
+    /// Sets `key` to `value`, with the 10 bytes starting at `stamp_offset` replaced by the
+    /// versionstamp of the transaction once it commits.
+    ///
+    /// The value must hold a placeholder for the versionstamp: its content is overwritten. The
+    /// little-endian offset expected by `MutationType::SetVersionstampedValue` is appended
+    /// internally, `value` should not contain it.
+    ///
+    /// The versionstamp can be retrieved with [`Transaction::get_versionstamp`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the versionstamp does not fit in `value`.
+    #[cfg_api_versions(min = 520)]
+    pub fn set_versionstamped_value(&self, key: &[u8], value: &[u8], stamp_offset: u32) {
+        assert!(
+            (stamp_offset as usize)
+                .checked_add(10)
+                .is_some_and(|end| end <= value.len()),
+            "versionstamp at offset {} does not fit in a value of {} bytes",
+            stamp_offset,
+            value.len()
+        );
+        let mut param = Vec::with_capacity(value.len() + 4);
+        param.extend_from_slice(value);
+        param.extend_from_slice(&stamp_offset.to_le_bytes());
+        self.atomic_op(key, &param, options::MutationType::SetVersionstampedValue)
+    }
+
     /// Resolves a key selector against the keys in the database snapshot represented by
     /// transaction.
     ///
@@ -542,6 +1126,38 @@ impl Transaction {
         })
     }
 
+    /// Resolves the key selector described by `base`, `or_equal` and `offset` without building a
+    /// [`KeySelector`].
+    ///
+    /// For instance, the first key greater than or equal to `base` is `(base, 1, false)` and the
+    /// last key strictly less than `base` is `(base, 0, false)`. See [`KeySelector`] for the
+    /// exact semantics.
+    ///
+    /// # Arguments
+    ///
+    /// * `base`: the anchor key of the selector
+    /// * `offset`: the number of keys to move from the anchor
+    /// * `or_equal`: whether the anchor key itself is included
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn resolve_key(
+        &self,
+        base: &[u8],
+        offset: i32,
+        or_equal: bool,
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<FdbSlice>> + Send + Sync + Unpin {
+        FdbFuture::new(unsafe {
+            fdb_sys::fdb_transaction_get_key(
+                self.inner.as_ptr(),
+                base.as_ptr(),
+                fdb_len(base.len(), "key"),
+                fdb_bool(or_equal),
+                offset,
+                fdb_bool(snapshot),
+            )
+        })
+    }
+
     /// Reads all key-value pairs in the database snapshot represented by transaction (potentially
     /// limited by limit, target_bytes, or mode) which have a key lexicographically greater than or
     /// equal to the key resolved by the begin key selector and lexicographically less than the key
@@ -552,6 +1168,9 @@ impl Transaction {
     /// This method is a little more efficient than `get_ranges_keyvalues` but a little harder to
     /// use.
     ///
+    /// The stream can be dropped between any two polls: the page being read is then cancelled,
+    /// while the pages already yielded stay valid.
+    ///
     /// # Arguments
     ///
     /// * `opt`: the range, limit, target_bytes and mode
@@ -578,6 +1197,43 @@ impl Transaction {
         })
     }
 
+    /// Same as [Transaction::get_ranges], but requests each page as soon as the previous one is
+    /// received, so that reading the next page overlaps with the processing of the current one.
+    ///
+    /// The first page is requested when this method is called. The begin of a page depends on
+    /// the last key of the previous one, so at most one page is read ahead of the consumer. An
+    /// error is yielded in order, after the pages preceding it, and ends the stream.
+    ///
+    /// Dropping the stream cancels the page read ahead.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_ranges_prefetched<'a>(
+        &'a self,
+        opt: RangeOption<'a>,
+        snapshot: bool,
+    ) -> impl Stream<Item = FdbResult<FdbValues>> + Send + Sync + Unpin + 'a {
+        let first = self.get_range(&opt, 1, snapshot);
+        stream::unfold(Some((1, opt, first)), move |state| {
+            if let Some((iteration, opt, page)) = state {
+                Either::Left(page.map(move |maybe_values| {
+                    let next = match &maybe_values {
+                        Ok(values) => opt.next_range(values).map(|next_opt| {
+                            let next_page = self.get_range(&next_opt, iteration + 1, snapshot);
+                            (iteration + 1, next_opt, next_page)
+                        }),
+                        Err(..) => None,
+                    };
+                    Some((maybe_values, next))
+                }))
+            } else {
+                Either::Right(future::ready(None))
+            }
+        })
+    }
+
     /// Reads all key-value pairs in the database snapshot represented by transaction (potentially
     /// limited by limit, target_bytes, or mode) which have a key lexicographically greater than or
     /// equal to the key resolved by the begin key selector and lexicographically less than the key
@@ -599,11 +1255,318 @@ impl Transaction {
             .try_flatten()
     }
 
+    /// Reads all key-value pairs of the range, page by page, and writes them to `writer` using
+    /// the given `format`.
+    ///
+    /// Each page is written as soon as it is received, so the range is never buffered in
+    /// memory as a whole. The writer is flushed once the range is exhausted.
+    ///
+    /// Returns the number of key-value pairs written. Errors raised by the writer are returned
+    /// as a `FdbBindingError::CustomError`.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    /// * `writer`: where the encoded key-value pairs are written
+    /// * `format`: how each key-value pair is encoded
+    pub async fn stream_range_to<W>(
+        &self,
+        opt: RangeOption<'_>,
+        snapshot: bool,
+        mut writer: W,
+        format: ExportFormat,
+    ) -> Result<u64, FdbBindingError>
+    where
+        W: AsyncWrite + Unpin,
+    {
+        let mut ranges = self.get_ranges(opt, snapshot);
+        let mut buf = Vec::new();
+        let mut count = 0;
+
+        while let Some(values) = ranges.try_next().await? {
+            buf.clear();
+            for kv in values.iter() {
+                format.encode_into(kv.key(), kv.value(), &mut buf);
+            }
+            writer
+                .write_all(&buf)
+                .await
+                .map_err(|e| FdbBindingError::new_custom_error(Box::new(e)))?;
+            count += values.len() as u64;
+        }
+        writer
+            .flush()
+            .await
+            .map_err(|e| FdbBindingError::new_custom_error(Box::new(e)))?;
+
+        Ok(count)
+    }
+
+    /// Reads all key-value pairs of the range and splits them in two, according to `pred`.
+    ///
+    /// Returns the pairs whose key matches `pred` first, then the others, each in range order.
+    /// The range is scanned only once.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    /// * `pred`: the predicate applied to each key
+    #[allow(clippy::type_complexity)]
+    pub async fn partition_range<F>(
+        &self,
+        opt: RangeOption<'_>,
+        snapshot: bool,
+        pred: F,
+    ) -> FdbResult<(Vec<(Vec<u8>, Vec<u8>)>, Vec<(Vec<u8>, Vec<u8>)>)>
+    where
+        F: Fn(&[u8]) -> bool,
+    {
+        let mut matching = Vec::new();
+        let mut others = Vec::new();
+
+        let mut ranges = self.get_ranges(opt, snapshot);
+        while let Some(values) = ranges.try_next().await? {
+            for kv in values.iter() {
+                let pair = (kv.key().to_vec(), kv.value().to_vec());
+                if pred(kv.key()) {
+                    matching.push(pair);
+                } else {
+                    others.push(pair);
+                }
+            }
+        }
+
+        Ok((matching, others))
+    }
+
+    /// Reads the key-value pairs of the range, page by page, passing each of them to `f`.
+    ///
+    /// Pairs are borrowed from the page being read, so nothing is copied or accumulated. The scan
+    /// stops as soon as `f` returns `ControlFlow::Break`, without requesting the next page.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    /// * `f`: called with the key and value of each pair, in range order
+    pub async fn for_each_in_range<F>(
+        &self,
+        opt: RangeOption<'_>,
+        snapshot: bool,
+        mut f: F,
+    ) -> FdbResult<()>
+    where
+        F: FnMut(&[u8], &[u8]) -> ControlFlow<()>,
+    {
+        let mut ranges = self.get_ranges(opt, snapshot);
+        while let Some(values) = ranges.try_next().await? {
+            for kv in values.iter() {
+                if f(kv.key(), kv.value()).is_break() {
+                    return Ok(());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads all key-value pairs of the range, unpacking each key as a `K` and each value as a
+    /// `V` with the tuple layer.
+    ///
+    /// Decoding stops at the first key or value that cannot be unpacked, which is returned as a
+    /// `FdbBindingError::PackError` naming the offending key.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub async fn get_range_typed<K, V>(
+        &self,
+        opt: RangeOption<'_>,
+        snapshot: bool,
+    ) -> Result<Vec<(K, V)>, FdbBindingError>
+    where
+        K: for<'de> TupleUnpack<'de>,
+        V: for<'de> TupleUnpack<'de>,
+    {
+        fn decode_error(what: &str, key: &[u8], err: PackError) -> FdbBindingError {
+            FdbBindingError::PackError(PackError::Message(
+                format!(
+                    "failed to unpack the {} of {}: {}",
+                    what,
+                    Bytes::from(key),
+                    err
+                )
+                .into(),
+            ))
+        }
+
+        let mut pairs = Vec::new();
+        let mut ranges = self.get_ranges(opt, snapshot);
+        while let Some(values) = ranges.try_next().await? {
+            pairs.reserve(values.len());
+            for kv in values.iter() {
+                let key = unpack(kv.key()).map_err(|err| decode_error("key", kv.key(), err))?;
+                let value =
+                    unpack(kv.value()).map_err(|err| decode_error("value", kv.key(), err))?;
+                pairs.push((key, value));
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Reads all key-value pairs of the range, decoding each value with `decoder`.
+    ///
+    /// Decoding stops at the first value that cannot be decoded, which is returned as a
+    /// `FdbBindingError::CustomError` holding a [ValueDecodeError] with the offending key.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    /// * `decoder`: how each value is decoded
+    pub async fn get_range_decode<V, D>(
+        &self,
+        opt: RangeOption<'_>,
+        snapshot: bool,
+        decoder: D,
+    ) -> Result<Vec<(Vec<u8>, V)>, FdbBindingError>
+    where
+        D: ValueDecoder<V>,
+    {
+        let mut pairs = Vec::new();
+        let mut ranges = self.get_ranges(opt, snapshot);
+        while let Some(values) = ranges.try_next().await? {
+            pairs.reserve(values.len());
+            for kv in values.iter() {
+                let value = decoder.decode(kv.value()).map_err(|err| {
+                    FdbBindingError::CustomError(Box::new(ValueDecodeError {
+                        key: kv.key().to_vec(),
+                        source: Box::new(err),
+                    }))
+                })?;
+                pairs.push((kv.key().to_vec(), value));
+            }
+        }
+        Ok(pairs)
+    }
+
+    /// Reads the key-value pairs of the range, page by page, until their keys and values add up
+    /// to `byte_limit` bytes.
+    ///
+    /// Returns the pairs read and whether the range has more pairs past them. The pairs read may
+    /// exceed the budget by up to a page, see `RangeOption::byte_limit`.
+    ///
+    /// # Arguments
+    ///
+    /// * `opt`: the range, limit, target_bytes and mode
+    /// * `byte_limit`: the budget of key and value bytes to read, overriding `opt.byte_limit`
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub async fn get_range_bounded_by_size(
+        &self,
+        opt: RangeOption<'_>,
+        byte_limit: usize,
+        snapshot: bool,
+    ) -> FdbResult<(Vec<FdbValue>, bool)> {
+        let mut next_opt = Some(RangeOption {
+            byte_limit: Some(byte_limit),
+            ..opt
+        });
+        let mut pairs = Vec::new();
+        let mut more = false;
+        let mut iteration = 1;
+        while let Some(opt) = next_opt.take() {
+            let values = self.get_range(&opt, iteration, snapshot).await?;
+            more = values.more();
+            next_opt = opt.next_range(&values);
+            pairs.extend(values);
+            iteration += 1;
+        }
+        Ok((pairs, more))
+    }
+
+    /// Reads the next page of a paginated range scan, of at most `page_size` key-value pairs.
+    ///
+    /// Returns the pairs read along with the cursor of the following page, or `None` once the
+    /// range, or the limit of the scan, is exhausted. A scan starts from a cursor built from a
+    /// `RangeOption`.
+    ///
+    /// # Arguments
+    ///
+    /// * `cursor`: where the previous page stopped
+    /// * `page_size`: the maximum number of pairs to read, at least one is read
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_range_from_cursor(
+        &self,
+        cursor: &RangeCursor,
+        page_size: usize,
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<(FdbValues, Option<RangeCursor>)>> + Send + Sync + Unpin
+    {
+        let opt = cursor.range_option();
+        let page = RangeOption {
+            limit: Some(
+                opt.limit
+                    .map_or(page_size, |limit| limit.min(page_size))
+                    .max(1),
+            ),
+            mode: options::StreamingMode::Exact,
+            ..opt.clone()
+        };
+        self.get_range(&page, 1, snapshot).map(move |values| {
+            let values = values?;
+            let next = opt.next_range(&values).map(RangeCursor::from);
+            Ok((values, next))
+        })
+    }
+
+    /// Resolves a key selector and reads the value of the key it resolves to, in a single round
+    /// trip.
+    ///
+    /// This is a range read of one key-value pair starting at `selector`, which saves the
+    /// `get_key` then `get` sequence. It returns the resolved key with its value, or `None` if the
+    /// selector resolves past the last key of the keyspace (`\xff`, or `\xff\xff` for a selector
+    /// on a system key).
+    ///
+    /// # Arguments
+    ///
+    /// * `selector`: the key selector
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn get_at_selector(
+        &self,
+        selector: KeySelector,
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<Option<(Vec<u8>, FdbSlice)>>> + Send + Sync + Unpin {
+        let end: &[u8] = if selector.key().starts_with(b"\xff") {
+            b"\xff\xff"
+        } else {
+            b"\xff"
+        };
+        let opt = RangeOption {
+            begin: selector,
+            end: KeySelector::first_greater_or_equal(end),
+            limit: Some(1),
+            mode: options::StreamingMode::Exact,
+            ..RangeOption::default()
+        };
+        self.get_range(&opt, 1, snapshot).map(|values| {
+            Ok(values?
+                .into_iter()
+                .next()
+                .map(|value| (value.key().to_vec(), value.into_value())))
+        })
+    }
+
     /// Reads all key-value pairs in the database snapshot represented by transaction (potentially
     /// limited by limit, target_bytes, or mode) which have a key lexicographically greater than or
     /// equal to the key resolved by the begin key selector and lexicographically less than the key
     /// resolved by the end key selector.
     ///
+    /// The C API has no key-only range read: the cluster always sends the values. Keys and values
+    /// are borrowed from the future's memory, so a scan that only looks at `FdbKeyValue::key`
+    /// copies no value.
+    ///
     /// # Arguments
     ///
     /// * `opt`: the range, limit, target_bytes and mode
@@ -621,7 +1584,7 @@ impl Transaction {
         let key_begin = begin.key();
         let key_end = end.key();
 
-        FdbFuture::new(unsafe {
+        let values = FdbFuture::new(unsafe {
             fdb_sys::fdb_transaction_get_range(
                 self.inner.as_ptr(),
                 key_begin.as_ptr(),
@@ -633,13 +1596,14 @@ impl Transaction {
                 fdb_bool(end.or_equal()),
                 end.offset(),
                 fdb_limit(opt.limit.unwrap_or(0)),
-                fdb_limit(opt.target_bytes),
+                fdb_limit(opt.page_target_bytes()),
                 opt.mode.code(),
                 fdb_iteration(iteration),
                 fdb_bool(snapshot),
                 fdb_bool(opt.reverse),
             )
-        })
+        });
+        trace::get_range(values, key_begin.len(), key_end.len(), iteration)
     }
 
     /// Mapped Range is an experimental feature introduced in FDB 7.1.
@@ -663,6 +1627,9 @@ impl Transaction {
     /// More info can be found in the relevant [documentation](https://github.com/apple/foundationdb/wiki/Everything-about-GetMappedRange#input).
     ///
     /// This is the "raw" version, users are expected to use [Transaction::get_mapped_ranges]
+    ///
+    /// Fails with `api_version_not_supported` (2203) if the runtime API version selected through
+    /// [`FdbApiBuilder`](crate::api::FdbApiBuilder) is older than 710.
     #[cfg_api_versions(min = 710)]
     pub fn get_mapped_range(
         &self,
@@ -671,12 +1638,16 @@ impl Transaction {
         iteration: usize,
         snapshot: bool,
     ) -> impl Future<Output = FdbResult<MappedKeyValues>> + Send + Sync + Unpin {
+        if let Err(err) = crate::api::ensure_api_version(710) {
+            return Either::Left(future::err(err));
+        }
+
         let begin = &opt.begin;
         let end = &opt.end;
         let key_begin = begin.key();
         let key_end = end.key();
 
-        FdbFuture::new(unsafe {
+        Either::Right(FdbFuture::new(unsafe {
             fdb_sys::fdb_transaction_get_mapped_range(
                 self.inner.as_ptr(),
                 key_begin.as_ptr(),
@@ -690,13 +1661,13 @@ impl Transaction {
                 mapper.as_ptr(),
                 fdb_len(mapper.len(), "mapper_length"),
                 fdb_limit(opt.limit.unwrap_or(0)),
-                fdb_limit(opt.target_bytes),
+                fdb_limit(opt.page_target_bytes()),
                 opt.mode.code(),
                 fdb_iteration(iteration),
                 fdb_bool(snapshot),
                 fdb_bool(opt.reverse),
             )
-        })
+        }))
     }
 
     /// Mapped Range is an experimental feature introduced in FDB 7.1.
@@ -743,6 +1714,57 @@ impl Transaction {
         })
     }
 
+    /// Reads the whole mapped range, page by page, as a flat stream of mapped key-values.
+    ///
+    /// Each page is requested through [Transaction::get_mapped_range], the next one starting
+    /// right after the last parent key of the previous page, until the range is exhausted.
+    ///
+    /// The mapper must reference the parent key-value through at least one `{K[n]}` or `{V[n]}`
+    /// placeholder, otherwise the stream yields a single `client_invalid_operation` (2000) error
+    /// without reading anything.
+    ///
+    /// **WARNING** : This feature is considered experimental at this time. It is only allowed when
+    /// using snapshot isolation AND disabling read-your-writes.
+    #[cfg_api_versions(min = 710)]
+    pub fn get_mapped_ranges_keyvalues<'a>(
+        &'a self,
+        opt: RangeOption<'a>,
+        mapper: &'a [u8],
+        snapshot: bool,
+    ) -> impl Stream<Item = FdbResult<crate::mapped_key_values::FdbMappedValue>> + Unpin + 'a {
+        if !mapper_has_placeholder(mapper) {
+            return Either::Left(stream::once(future::err(FdbError::new(2000))));
+        }
+        Either::Right(
+            self.get_mapped_ranges(opt, mapper, snapshot)
+                .map_ok(|values| stream::iter(values.into_iter().map(Ok)))
+                .try_flatten(),
+        )
+    }
+
+    /// Issues one mapped read per range concurrently, all using the same mapper.
+    ///
+    /// The returned vector holds one `MappedKeyValues` per input range, in the same order as
+    /// `ranges`. Each of them is the first batch returned by [Transaction::get_mapped_range]; use
+    /// `MappedKeyValues::more` and [Transaction::get_mapped_ranges] to read the remainder of a
+    /// range.
+    ///
+    /// **WARNING** : This feature is considered experimental at this time. It is only allowed when
+    /// using snapshot isolation AND disabling read-your-writes.
+    #[cfg_api_versions(min = 710)]
+    pub fn get_mapped_multi(
+        &self,
+        ranges: Vec<RangeOption>,
+        mapper: &[u8],
+        snapshot: bool,
+    ) -> impl Future<Output = FdbResult<Vec<MappedKeyValues>>> + Send + Sync + Unpin {
+        future::try_join_all(
+            ranges
+                .iter()
+                .map(|opt| self.get_mapped_range(opt, mapper, 1, snapshot)),
+        )
+    }
+
     /// Modify the database snapshot represented by transaction to remove all keys (if any) which
     /// are lexicographically greater than or equal to the given begin key and lexicographically
     /// less than the given end_key.
@@ -761,6 +1783,75 @@ impl Transaction {
         }
     }
 
+    /// Modify the database snapshot represented by transaction to remove all keys (if any) which
+    /// start with the given prefix.
+    ///
+    /// This clears `[prefix, strinc(prefix))`, see [`strinc`](crate::strinc). If `prefix` is
+    /// empty, every normal key is cleared, up to `\xff`. If it is `\xff`, the system keys are
+    /// cleared up to `\xff\xff`: the special key space is never cleared, and a prefix of two or
+    /// more `0xff` bytes, which only matches special keys, clears nothing.
+    ///
+    /// The modification affects the actual database only if transaction is later committed with
+    /// `Transaction::commit`.
+    pub fn clear_range_starts_with(&self, prefix: &[u8]) {
+        match crate::strinc(prefix) {
+            Some(end) => self.clear_range(prefix, &end),
+            None if prefix.is_empty() => self.clear_range(prefix, b"\xff"),
+            None if prefix < &b"\xff\xff"[..] => self.clear_range(prefix, b"\xff\xff"),
+            None => {}
+        }
+    }
+
+    /// Clears each of the given `(begin, end)` ranges, as `clear_range` does.
+    ///
+    /// In debug builds, this panics if a range ends before it begins or if two non-empty ranges
+    /// overlap, which usually means the same keys are cleared twice by mistake.
+    ///
+    /// The modification affects the actual database only if transaction is later committed with
+    /// `Transaction::commit`.
+    pub fn clear_ranges(&self, ranges: &[(&[u8], &[u8])]) {
+        debug_assert!(
+            ranges.iter().all(|(begin, end)| begin <= end),
+            "a range to clear ends before it begins"
+        );
+        debug_assert!(ranges_are_disjoint(ranges), "ranges to clear overlap");
+        for (begin, end) in ranges {
+            self.clear_range(begin, end);
+        }
+    }
+
+    /// Returns the read conflict ranges that made the commit of this transaction fail with a
+    /// not_committed error.
+    ///
+    /// The ranges are only recorded if `TransactionOption::ReportConflictingKeys` was set
+    /// before committing, and they are read from the `\xff\xff/transaction/conflicting_keys/`
+    /// special key space: call this through [TransactionCommitError::get_conflicting_keys],
+    /// before `on_error` resets the transaction. Returns no range otherwise.
+    #[cfg_api_versions(min = 630)]
+    pub async fn get_conflicting_keys(&self) -> FdbResult<Vec<Range<Vec<u8>>>> {
+        const PREFIX: &[u8] = b"\xff\xff/transaction/conflicting_keys/";
+        const END: &[u8] = b"\xff\xff/transaction/conflicting_keys/\xff";
+
+        let opt = RangeOption {
+            mode: options::StreamingMode::WantAll,
+            ..RangeOption::from((PREFIX, END))
+        };
+        // each range is reported as its begin key, with the value `1`, followed by its end key,
+        // with the value `0`
+        let mut ranges = Vec::new();
+        let mut begin = None;
+        let mut boundaries = self.get_ranges_keyvalues(opt, false);
+        while let Some(kv) = boundaries.try_next().await? {
+            let key = kv.key()[PREFIX.len()..].to_vec();
+            match (kv.value(), begin.take()) {
+                (b"1", _) => begin = Some(key),
+                (b"0", Some(begin)) => ranges.push(begin..key),
+                _ => {}
+            }
+        }
+        Ok(ranges)
+    }
+
     /// Get the estimated byte size of the key range based on the byte sample collected by FDB
     #[cfg_api_versions(min = 630)]
     pub fn get_estimated_range_size_bytes(
@@ -803,14 +1894,34 @@ impl Transaction {
     /// snapshot reads or the transaction option for disabling “read-your-writes” has been invoked,
     /// any outstanding reads will immediately return errors.
     pub fn commit(self) -> impl Future<Output = TransactionResult> + Send + Sync + Unpin {
-        FdbFuture::<()>::new(unsafe { fdb_sys::fdb_transaction_commit(self.inner.as_ptr()) }).map(
-            move |r| match r {
-                Ok(()) => Ok(TransactionCommitted { tr: self }),
-                Err(err) => Err(TransactionCommitError { tr: self, err }),
-            },
+        trace::commit(
+            FdbFuture::<()>::new(unsafe { fdb_sys::fdb_transaction_commit(self.inner.as_ptr()) })
+                .map(move |r| match r {
+                    Ok(()) => Ok(TransactionCommitted { tr: self }),
+                    Err(err) => Err(TransactionCommitError { tr: self, err }),
+                }),
         )
     }
 
+    /// Commits the transaction and returns the version at which it was committed.
+    ///
+    /// This is `commit` followed by `TransactionCommitted::committed_version`, see its
+    /// documentation for the caveats of committed versions. As read-only transactions do not have
+    /// a committed version, committing one resolves to a `client_invalid_operation` error, even
+    /// though the commit itself succeeded.
+    pub fn commit_and_get_version(
+        self,
+    ) -> impl Future<Output = FdbResult<i64>> + Send + Sync + Unpin {
+        self.commit().map(|result| {
+            let version = result?.committed_version()?;
+            if version < 0 {
+                // client_invalid_operation
+                return Err(FdbError::new(2000));
+            }
+            Ok(version)
+        })
+    }
+
     /// Implements the recommended retry and backoff behavior for a transaction. This function knows
     /// which of the error codes generated by other `Transaction` functions represent temporary
     /// error conditions and which represent application errors that should be handled by the
@@ -827,10 +1938,11 @@ impl Transaction {
         self,
         err: FdbError,
     ) -> impl Future<Output = FdbResult<Transaction>> + Send + Sync + Unpin {
-        FdbFuture::<()>::new(unsafe {
+        let on_error = FdbFuture::<()>::new(unsafe {
             fdb_sys::fdb_transaction_on_error(self.inner.as_ptr(), err.code())
         })
-        .map_ok(|()| self)
+        .map_ok(|()| self);
+        trace::on_error(on_error, err.code())
     }
 
     /// Cancels the transaction. All pending or future uses of the transaction will return a
@@ -879,9 +1991,9 @@ impl Transaction {
     /// reported a change. When this number is exceeded, an attempt to create a watch will return a
     /// too_many_watches error. This limit can be changed using the MAX_WATCHES database option.
     /// Because a watch outlives the transaction that creates it, any watch that is no longer
-    /// needed should be cancelled by dropping its future.
-    pub fn watch(&self, key: &[u8]) -> impl Future<Output = FdbResult<()>> + Send + Sync + Unpin {
-        FdbFuture::new(unsafe {
+    /// needed should be cancelled by dropping its future, or by calling [FdbWatch::cancel].
+    pub fn watch(&self, key: &[u8]) -> FdbWatch {
+        FdbWatch::new(unsafe {
             fdb_sys::fdb_transaction_watch(
                 self.inner.as_ptr(),
                 key.as_ptr(),
@@ -890,6 +2002,26 @@ impl Transaction {
         })
     }
 
+    /// Same as [Transaction::watch], but gives up waiting once `timeout` is elapsed.
+    ///
+    /// The returned future resolves to `Ok(true)` if the watch fired and to `Ok(false)` on
+    /// timeout. The timeout starts when this method is called, not when the future is first
+    /// polled. As with `watch`, the transaction must be committed for the watch to be active.
+    ///
+    /// On timeout the underlying watch is cancelled and its native future freed, so it does not
+    /// count against the MAX_WATCHES limit anymore.
+    pub fn watch_with_timeout(
+        &self,
+        key: &[u8],
+        timeout: Duration,
+    ) -> impl Future<Output = FdbResult<bool>> + Send + Sync + Unpin {
+        future::select(self.watch(key), timer::delay(timeout)).map(|either| match either {
+            Either::Left((watched, _delay)) => watched.map(|()| true),
+            // dropping the pending watch future destroys, and thus cancels, the native future
+            Either::Right(((), _watch)) => Ok(false),
+        })
+    }
+
     /// Returns an FDBFuture which will be set to the approximate transaction size so far in the
     /// returned future, which is the summation of the estimated size of mutations, read conflict
     /// ranges, and write conflict ranges.
@@ -945,7 +2077,9 @@ impl Transaction {
     /// compromised by transaction options) is guaranteed to represent all transactions which were
     /// reported committed before that call.
     pub fn get_read_version(&self) -> impl Future<Output = FdbResult<i64>> + Send + Sync + Unpin {
-        FdbFuture::new(unsafe { fdb_sys::fdb_transaction_get_read_version(self.inner.as_ptr()) })
+        trace::get_read_version(FdbFuture::new(unsafe {
+            fdb_sys::fdb_transaction_get_read_version(self.inner.as_ptr())
+        }))
     }
 
     /// Sets the snapshot read version used by a transaction.
@@ -1010,12 +2144,16 @@ impl Transaction {
     /// In order to protect against a race condition with cancel(), this call require a mutable
     /// access to the transaction.
     ///
-    /// This is similar to dropping the transaction and creating a new one.
+    /// This is similar to dropping the transaction and creating a new one: the read version,
+    /// the uncommitted mutations and the options are discarded, but the underlying handle is
+    /// reused. Futures obtained from the transaction before the reset are invalidated and should
+    /// not be relied upon.
     ///
     /// It is not necessary to call `reset()` when handling an error with `on_error()` since the
     /// transaction has already been reset.
     pub fn reset(&mut self) {
         unsafe { fdb_sys::fdb_transaction_reset(self.inner.as_ptr()) }
+        self.options.get_mut().unwrap().clear();
     }
 
     /// Adds a conflict range to a transaction without performing the associated read or write.
@@ -1051,6 +2189,102 @@ impl Drop for Transaction {
     }
 }
 
+/// A read-only view of a [`Transaction`] where every read is a
+/// [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots).
+///
+/// Snapshot reads do not add read conflict ranges to the transaction, so they will not cause it
+/// to conflict with concurrent writes of the keys that were read.
+///
+/// Obtained with [`Transaction::snapshot`].
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotTransaction<'t> {
+    trx: &'t Transaction,
+}
+
+impl<'t> SnapshotTransaction<'t> {
+    /// Snapshot version of [`Transaction::get`].
+    pub fn get(
+        &self,
+        key: &[u8],
+    ) -> impl Future<Output = FdbResult<Option<FdbSlice>>> + Send + Sync + Unpin {
+        self.trx.get(key, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_multi`].
+    pub fn get_multi<'a>(
+        &self,
+        keys: impl IntoIterator<Item = &'a [u8]>,
+    ) -> impl Future<Output = FdbResult<Vec<Option<FdbSlice>>>> + Send + Sync + Unpin {
+        self.trx.get_multi(keys, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_key`].
+    pub fn get_key(
+        &self,
+        selector: &KeySelector,
+    ) -> impl Future<Output = FdbResult<FdbSlice>> + Send + Sync + Unpin {
+        self.trx.get_key(selector, true)
+    }
+
+    /// Snapshot version of [`Transaction::resolve_key`].
+    pub fn resolve_key(
+        &self,
+        base: &[u8],
+        offset: i32,
+        or_equal: bool,
+    ) -> impl Future<Output = FdbResult<FdbSlice>> + Send + Sync + Unpin {
+        self.trx.resolve_key(base, offset, or_equal, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_range`].
+    pub fn get_range(
+        &self,
+        opt: &RangeOption,
+        iteration: usize,
+    ) -> impl Future<Output = FdbResult<FdbValues>> + Send + Sync + Unpin {
+        self.trx.get_range(opt, iteration, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_ranges`].
+    pub fn get_ranges<'a>(
+        &self,
+        opt: RangeOption<'a>,
+    ) -> impl Stream<Item = FdbResult<FdbValues>> + Send + Sync + Unpin + 'a
+    where
+        't: 'a,
+    {
+        self.trx.get_ranges(opt, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_ranges_keyvalues`].
+    pub fn get_ranges_keyvalues<'a>(
+        &self,
+        opt: RangeOption<'a>,
+    ) -> impl Stream<Item = FdbResult<FdbValue>> + Unpin + 'a
+    where
+        't: 'a,
+    {
+        self.trx.get_ranges_keyvalues(opt, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_mapped_range`].
+    #[cfg_api_versions(min = 710)]
+    pub fn get_mapped_range(
+        &self,
+        opt: &RangeOption,
+        mapper: &[u8],
+        iteration: usize,
+    ) -> impl Future<Output = FdbResult<MappedKeyValues>> + Send + Sync + Unpin {
+        self.trx.get_mapped_range(opt, mapper, iteration, true)
+    }
+
+    /// Snapshot version of [`Transaction::get_metadata_version`].
+    #[cfg_api_versions(min = 610)]
+    pub async fn get_metadata_version(&self) -> FdbResult<Option<i64>> {
+        self.trx.get_metadata_version(true).await
+    }
+}
+
 /// A retryable transaction, generated by Database.run
 #[derive(Clone)]
 pub struct RetryableTransaction {
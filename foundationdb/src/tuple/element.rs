@@ -7,6 +7,11 @@ use num_bigint::Sign;
 #[cfg(feature = "num-bigint")]
 use std::convert::TryFrom;
 
+/// A dynamically typed tuple element.
+///
+/// Elements compare like their packed representation: `a < b` if and only if
+/// `pack(&a) < pack(&b)`. A root-level `Element::Tuple` packs, and compares, as the sequence of
+/// its elements.
 #[derive(Clone, Debug)]
 pub enum Element<'a> {
     Nil,
@@ -228,3 +233,128 @@ impl<'a> Element<'a> {
         }
     }
 }
+
+/// Each element is represented by a single-entry map whose key names the variant, e.g.
+/// `{"int": 5}`, `{"string": "abc"}` or `{"tuple": [{"nil": null}, {"bool": true}]}`.
+///
+/// Bytes and versionstamps are encoded in padded standard base64, big integers as decimal
+/// strings and uuids in their hyphenated form.
+#[cfg(feature = "serde")]
+mod element_serde {
+    use super::*;
+    use base64::engine::general_purpose::STANDARD as BASE64;
+    use base64::Engine;
+    use serde::de::{self, MapAccess, Visitor};
+    use serde::ser::{Serialize, Serializer};
+    use serde::{Deserialize, Deserializer};
+    use std::fmt;
+
+    const VARIANTS: &[&str] = &[
+        "nil",
+        "bytes",
+        "string",
+        "tuple",
+        "int",
+        "bigint",
+        "float",
+        "double",
+        "bool",
+        "uuid",
+        "versionstamp",
+    ];
+
+    impl<'a> Serialize for Element<'a> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            fn variant<S: Serializer, T: Serialize + ?Sized>(
+                serializer: S,
+                index: u32,
+                value: &T,
+            ) -> Result<S::Ok, S::Error> {
+                serializer.serialize_newtype_variant(
+                    "Element",
+                    index,
+                    VARIANTS[index as usize],
+                    value,
+                )
+            }
+
+            match self {
+                Element::Nil => variant(serializer, 0, &()),
+                Element::Bytes(v) => variant(serializer, 1, &BASE64.encode(v)),
+                Element::String(v) => variant(serializer, 2, v),
+                Element::Tuple(v) => variant(serializer, 3, v),
+                Element::Int(v) => variant(serializer, 4, v),
+                #[cfg(feature = "num-bigint")]
+                Element::BigInt(v) => variant(serializer, 5, &v.to_string()),
+                Element::Float(v) => variant(serializer, 6, v),
+                Element::Double(v) => variant(serializer, 7, v),
+                Element::Bool(v) => variant(serializer, 8, v),
+                #[cfg(feature = "uuid")]
+                Element::Uuid(v) => variant(serializer, 9, &v.hyphenated().to_string()),
+                Element::Versionstamp(v) => variant(serializer, 10, &BASE64.encode(v.as_bytes())),
+            }
+        }
+    }
+
+    impl<'de, 'a> Deserialize<'de> for Element<'a> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_map(ElementVisitor(std::marker::PhantomData))
+        }
+    }
+
+    struct ElementVisitor<'a>(std::marker::PhantomData<Element<'a>>);
+
+    impl<'de, 'a> Visitor<'de> for ElementVisitor<'a> {
+        type Value = Element<'a>;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            f.write_str("a map with a single tuple element")
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+            let tag: String = map
+                .next_key()?
+                .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+            let element = match tag.as_str() {
+                "nil" => {
+                    map.next_value::<()>()?;
+                    Element::Nil
+                }
+                "bytes" => {
+                    let encoded: String = map.next_value()?;
+                    let bytes = BASE64.decode(&encoded).map_err(de::Error::custom)?;
+                    Element::Bytes(Bytes::from(bytes))
+                }
+                "string" => Element::String(Cow::Owned(map.next_value()?)),
+                "tuple" => Element::Tuple(map.next_value()?),
+                "int" => Element::Int(map.next_value()?),
+                #[cfg(feature = "num-bigint")]
+                "bigint" => {
+                    let decimal: String = map.next_value()?;
+                    Element::BigInt(decimal.parse().map_err(de::Error::custom)?)
+                }
+                "float" => Element::Float(map.next_value()?),
+                "double" => Element::Double(map.next_value()?),
+                "bool" => Element::Bool(map.next_value()?),
+                #[cfg(feature = "uuid")]
+                "uuid" => {
+                    let hyphenated: String = map.next_value()?;
+                    Element::Uuid(uuid::Uuid::parse_str(&hyphenated).map_err(de::Error::custom)?)
+                }
+                "versionstamp" => {
+                    let encoded: String = map.next_value()?;
+                    let bytes = BASE64.decode(&encoded).map_err(de::Error::custom)?;
+                    let bytes: [u8; 12] = bytes
+                        .try_into()
+                        .map_err(|_| de::Error::custom("a versionstamp must be 12 bytes long"))?;
+                    Element::Versionstamp(Versionstamp::from(bytes))
+                }
+                other => return Err(de::Error::unknown_variant(other, VARIANTS)),
+            };
+            if map.next_key::<de::IgnoredAny>()?.is_some() {
+                return Err(de::Error::invalid_length(2, &self));
+            }
+            Ok(element)
+        }
+    }
+}
@@ -1,6 +1,28 @@
 //! Implementation of the official tuple layer typecodes
 //!
 //! The official specification can be found [here](https://github.com/apple/foundationdb/blob/master/design/tuple.md).
+//!
+//! Structs can derive [`TuplePack`] and [`TupleUnpack`], their fields being encoded in
+//! declaration order like a tuple would be. Fields marked with `#[tuple(skip)]` are not encoded
+//! and are set to their `Default` value when unpacking.
+//!
+//! ```rust
+//! use foundationdb::tuple::{pack, unpack, TuplePack, TupleUnpack};
+//!
+//! #[derive(Debug, PartialEq, TuplePack, TupleUnpack)]
+//! struct UserKey {
+//!     tenant: String,
+//!     id: i64,
+//!     #[tuple(skip)]
+//!     cached: bool,
+//! }
+//!
+//! let key = UserKey { tenant: "acme".to_string(), id: 42, cached: true };
+//! let packed = pack(&key);
+//! assert_eq!(packed, pack(&("acme", 42)));
+//! let unpacked: UserKey = unpack(&packed).unwrap();
+//! assert_eq!(unpacked, UserKey { cached: false, ..key });
+//! ```
 
 mod element;
 pub mod hca;
@@ -18,6 +40,7 @@ use std::result;
 pub use uuid::Uuid;
 
 pub use element::Element;
+pub use foundationdb_macros::{TuplePack, TupleUnpack};
 pub use pack::{TuplePack, TupleUnpack, VersionstampOffset};
 pub use subspace::Subspace;
 pub use versionstamp::Versionstamp;
@@ -109,7 +132,9 @@ pub type PackResult<T> = result::Result<T, PackError>;
 
 /// Represent a sequence of bytes (i.e. &[u8])
 ///
-/// This sequence can be either owned or borrowed.
+/// This sequence can be either owned or borrowed. When unpacked, it borrows from the input
+/// unless it contains escaped nul bytes, in which case it has to be unescaped into an owned
+/// buffer. The same holds for `Cow<str>` and for the bytes and strings of an [Element].
 #[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Bytes<'a>(pub Cow<'a, [u8]>);
 
@@ -219,9 +244,34 @@ pub fn unpack<'de, T: TupleUnpack<'de>>(input: &'de [u8]) -> PackResult<T> {
     T::unpack_root(input)
 }
 
+/// Unpacks `input` as a sequence of [Element]s, packs them back and checks that the result is
+/// identical to `input`.
+///
+/// This is meant to help fuzzing the tuple codec. The round-trip only holds for canonical
+/// encodings, that is bytes produced by [pack]: some inputs are accepted by the decoder without
+/// being what the encoder would produce, integers padded with leading zero bytes for example.
+/// Those are reported as a `PackError::Message`, like any other mismatch.
+pub fn roundtrip_check(input: &[u8]) -> PackResult<()> {
+    let elements: Vec<Element> = unpack(input)?;
+    let packed = pack(&elements);
+    if packed == input {
+        Ok(())
+    } else {
+        Err(PackError::Message(
+            format!(
+                "tuple round-trip mismatch: {} re-encoded as {}",
+                Bytes::from(input),
+                Bytes::from(packed)
+            )
+            .into_boxed_str(),
+        ))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::{rngs::SmallRng, Rng, SeedableRng};
 
     const NIL_VAL: Option<()> = None;
 
@@ -556,6 +606,47 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono() {
+        use chrono::{DateTime, TimeZone, Utc};
+
+        let pre_epoch = Utc.timestamp_opt(-1_234_567_890, 123_456_789).unwrap();
+        let post_epoch = Utc.timestamp_opt(1_234_567_890, 987_654_321).unwrap();
+        for datetime in [pre_epoch, post_epoch, Utc.timestamp_nanos(0)] {
+            let packed = pack(&datetime);
+            assert_eq!(unpack::<DateTime<Utc>>(&packed).unwrap(), datetime);
+            assert_eq!(packed, pack(&datetime.timestamp_nanos_opt().unwrap()));
+        }
+        assert!(pack(&pre_epoch) < pack(&Utc.timestamp_nanos(0)));
+        assert!(pack(&Utc.timestamp_nanos(0)) < pack(&post_epoch));
+
+        // outside of the i64 nanoseconds range (1677 to 2262)
+        let year_1000 = Utc.timestamp_opt(-30_610_224_000, 1).unwrap();
+        let year_3000 = Utc.timestamp_opt(32_503_680_000, 999_999_999).unwrap();
+        for datetime in [
+            DateTime::<Utc>::MIN_UTC,
+            year_1000,
+            year_3000,
+            DateTime::<Utc>::MAX_UTC,
+        ] {
+            assert!(datetime.timestamp_nanos_opt().is_none());
+            assert_eq!(unpack::<DateTime<Utc>>(&pack(&datetime)).unwrap(), datetime);
+        }
+        let sorted = [
+            DateTime::<Utc>::MIN_UTC,
+            year_1000,
+            pre_epoch,
+            post_epoch,
+            year_3000,
+            DateTime::<Utc>::MAX_UTC,
+        ];
+        assert!(sorted
+            .windows(2)
+            .all(|pair| pack(&pair[0]) < pack(&pair[1])));
+        assert!(unpack::<DateTime<Utc>>(&pack(&i128::MAX)).is_err());
+    }
+
     #[test]
     fn test_bindingtester() {
         test_serde("NEW_TRANSACTION".to_string(), b"\x02NEW_TRANSACTION\x00");
@@ -666,4 +757,332 @@ mod tests {
             )
         );
     }
+
+    fn random_bytes(rng: &mut SmallRng) -> Vec<u8> {
+        let len = rng.gen_range(0..16);
+        (0..len).map(|_| rng.gen()).collect()
+    }
+
+    fn random_element(rng: &mut SmallRng, depth: usize) -> Element<'static> {
+        match rng.gen_range(0..if depth < 3 { 10 } else { 9 }) {
+            0 => Element::Nil,
+            1 => Element::Bytes(random_bytes(rng).into()),
+            2 => {
+                let len = rng.gen_range(0..16);
+                Element::String(Cow::Owned(
+                    (0..len).map(|_| rng.gen::<char>()).collect::<String>(),
+                ))
+            }
+            3 => Element::Int(rng.gen()),
+            4 => Element::Int(rng.gen_range(-300..300)),
+            5 => Element::Float(f32::from_bits(rng.gen())),
+            6 => Element::Double(f64::from_bits(rng.gen())),
+            7 => Element::Bool(rng.gen()),
+            8 => Element::Versionstamp(Versionstamp::complete(rng.gen(), rng.gen())),
+            _ => {
+                let len = rng.gen_range(0..4);
+                Element::Tuple((0..len).map(|_| random_element(rng, depth + 1)).collect())
+            }
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_check_random() {
+        let mut rng = SmallRng::seed_from_u64(0x5eed);
+        for _ in 0..10_000 {
+            let len = rng.gen_range(0..6);
+            let tuple: Vec<Element> = (0..len).map(|_| random_element(&mut rng, 1)).collect();
+            let packed = pack(&tuple);
+            roundtrip_check(&packed).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_float_ordering() {
+        // the sign bit of positive values is flipped, negative values are fully inverted
+        assert_eq!(pack(&1.0f32), b"\x20\xbf\x80\x00\x00");
+        assert_eq!(pack(&-1.0f32), b"\x20\x40\x7f\xff\xff");
+        assert_eq!(pack(&0.0f64), b"\x21\x80\x00\x00\x00\x00\x00\x00\x00");
+        assert_eq!(pack(&-0.0f64), b"\x21\x7f\xff\xff\xff\xff\xff\xff\xff");
+
+        // NaNs sort beyond the infinities, on the side of their sign
+        let floats = [
+            -f32::NAN,
+            f32::NEG_INFINITY,
+            f32::MIN,
+            -1.0,
+            -f32::MIN_POSITIVE,
+            -0.0,
+            0.0,
+            f32::MIN_POSITIVE,
+            1.0,
+            f32::MAX,
+            f32::INFINITY,
+            f32::NAN,
+        ];
+        let packed: Vec<_> = floats.iter().map(pack).collect();
+        assert!(packed.windows(2).all(|w| w[0] < w[1]), "{:?}", packed);
+        for (f, packed) in floats.iter().zip(&packed) {
+            assert_eq!(unpack::<f32>(packed).unwrap().to_bits(), f.to_bits());
+        }
+
+        let doubles = [
+            -f64::NAN,
+            f64::NEG_INFINITY,
+            f64::MIN,
+            -1.0,
+            -f64::MIN_POSITIVE,
+            -0.0,
+            0.0,
+            f64::MIN_POSITIVE,
+            1.0,
+            f64::MAX,
+            f64::INFINITY,
+            f64::NAN,
+        ];
+        let packed: Vec<_> = doubles.iter().map(pack).collect();
+        assert!(packed.windows(2).all(|w| w[0] < w[1]), "{:?}", packed);
+        for (f, packed) in doubles.iter().zip(&packed) {
+            assert_eq!(unpack::<f64>(packed).unwrap().to_bits(), f.to_bits());
+        }
+    }
+
+    #[test]
+    fn test_bool() {
+        test_serde((true, false), &[0x27, 0x26]);
+
+        // false sorts before true, whatever follows
+        assert!(pack(&(false, 1)) < pack(&(true, 0)));
+        assert!(pack(&false) < pack(&true));
+
+        // other type codes are not booleans
+        assert!(unpack::<bool>(&pack(&1i64)).is_err());
+        assert!(unpack::<bool>(&pack(&0i64)).is_err());
+        assert!(unpack::<bool>(&pack(&())).is_err());
+        assert!(unpack::<bool>(&[]).is_err());
+    }
+
+    #[test]
+    fn test_ip_ordering() {
+        use std::net::{Ipv4Addr, Ipv6Addr};
+
+        assert_eq!(
+            pack(&Ipv4Addr::new(10, 0, 0, 1)),
+            b"\x01\x0a\x00\xff\x00\xff\x01\x00"
+        );
+        assert_eq!(pack(&Ipv6Addr::LOCALHOST).len(), 1 + 15 * 2 + 1 + 1);
+
+        let v4 = [
+            Ipv4Addr::UNSPECIFIED,
+            Ipv4Addr::new(0, 0, 0, 1),
+            Ipv4Addr::new(0, 0, 1, 0),
+            Ipv4Addr::new(9, 255, 255, 255),
+            Ipv4Addr::new(10, 0, 0, 0),
+            Ipv4Addr::new(127, 0, 0, 1),
+            Ipv4Addr::new(192, 168, 0, 1),
+            Ipv4Addr::BROADCAST,
+        ];
+        let packed: Vec<_> = v4.iter().map(pack).collect();
+        assert!(packed.windows(2).all(|w| w[0] < w[1]), "{:?}", packed);
+        for (addr, packed) in v4.iter().zip(&packed) {
+            assert_eq!(&unpack::<Ipv4Addr>(packed).unwrap(), addr);
+        }
+
+        let v6 = [
+            Ipv6Addr::UNSPECIFIED,
+            Ipv6Addr::LOCALHOST,
+            Ipv6Addr::new(0, 0, 0, 0, 0, 0xffff, 0x7f00, 1),
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0),
+            Ipv6Addr::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+            Ipv6Addr::new(0xfe80, 0, 0, 0, 0, 0, 0, 0),
+            Ipv6Addr::new(
+                0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff, 0xffff,
+            ),
+        ];
+        let packed: Vec<_> = v6.iter().map(pack).collect();
+        assert!(packed.windows(2).all(|w| w[0] < w[1]), "{:?}", packed);
+        for (addr, packed) in v6.iter().zip(&packed) {
+            assert_eq!(&unpack::<Ipv6Addr>(packed).unwrap(), addr);
+        }
+
+        // byte strings of another length are not addresses
+        assert!(unpack::<Ipv4Addr>(&pack(&Ipv6Addr::LOCALHOST)).is_err());
+        assert!(unpack::<Ipv6Addr>(&pack(&Ipv4Addr::LOCALHOST)).is_err());
+        assert!(unpack::<Ipv4Addr>(&pack(&"1.2.3.4")).is_err());
+    }
+
+    #[test]
+    fn test_element_ord_matches_packed() {
+        let elements = vec![
+            Element::Nil,
+            Element::Bytes(Bytes::from(&b""[..])),
+            Element::Bytes(Bytes::from(&b"\x00"[..])),
+            Element::Bytes(Bytes::from(&b"\x00\xff"[..])),
+            Element::Bytes(Bytes::from(&b"a"[..])),
+            Element::String("".into()),
+            Element::String("a".into()),
+            Element::String("a\0".into()),
+            Element::Tuple(vec![]),
+            Element::Tuple(vec![Element::Nil]),
+            Element::Tuple(vec![Element::Nil, Element::Int(0)]),
+            Element::Tuple(vec![Element::Tuple(vec![Element::Nil])]),
+            Element::Tuple(vec![Element::Int(-1), Element::Nil]),
+            Element::Int(i64::MIN),
+            Element::Int(-256),
+            Element::Int(-255),
+            Element::Int(-1),
+            Element::Int(0),
+            Element::Int(1),
+            Element::Int(255),
+            Element::Int(256),
+            Element::Int(i64::MAX),
+            Element::Float(f32::NEG_INFINITY),
+            Element::Float(-0.0),
+            Element::Float(0.0),
+            Element::Float(f32::NAN),
+            Element::Double(-1.0),
+            Element::Double(1.0),
+            Element::Bool(false),
+            Element::Bool(true),
+            Element::Versionstamp(Versionstamp::complete([0; 10], 1)),
+            Element::Versionstamp(Versionstamp::incomplete(0)),
+        ];
+        // a root-level `Element::Tuple` packs as its elements, each element is wrapped to be
+        // compared as a one-element tuple, then as a nested tuple
+        let wrap = |e: &Element<'static>| Element::Tuple(vec![e.clone()]);
+        for (i, a) in elements.iter().enumerate() {
+            for (j, b) in elements.iter().enumerate() {
+                let (a, b) = (wrap(a), wrap(b));
+                assert_eq!(a.cmp(&b), i.cmp(&j), "{:?} {:?}", a, b);
+                assert_eq!(pack(&a).cmp(&pack(&b)), i.cmp(&j), "{:?} {:?}", a, b);
+                let (a, b) = (wrap(&a), wrap(&b));
+                assert_eq!(a.cmp(&b), i.cmp(&j), "{:?} {:?}", a, b);
+                assert_eq!(pack(&a).cmp(&pack(&b)), i.cmp(&j), "{:?} {:?}", a, b);
+            }
+        }
+
+        let mut rng = SmallRng::seed_from_u64(0x0bde5);
+        for _ in 0..10_000 {
+            let a = random_element(&mut rng, 1);
+            let b = random_element(&mut rng, 1);
+            assert_eq!(a.cmp(&b), pack(&a).cmp(&pack(&b)), "{:?} {:?}", a, b);
+            assert_eq!(a == b, pack(&a) == pack(&b), "{:?} {:?}", a, b);
+        }
+    }
+
+    #[test]
+    fn test_roundtrip_check_rejects() {
+        roundtrip_check(b"").unwrap();
+        roundtrip_check(b"\x02foo\x00\x05\x00\xff\x14\x00").unwrap();
+        // malformed: missing string terminator
+        assert!(roundtrip_check(b"\x02foo").is_err());
+        // non canonical: 0 encoded on one byte instead of INTZERO
+        assert!(matches!(
+            roundtrip_check(b"\x15\x00"),
+            Err(PackError::Message(_))
+        ));
+    }
+
+    #[test]
+    fn test_unpack_borrows_without_nul() {
+        let packed = pack(&(Bytes::from(&b"blob"[..]), "text"));
+        let (bytes, string): (Bytes, Cow<str>) = unpack(&packed).unwrap();
+        assert!(matches!(bytes.0, Cow::Borrowed(b"blob")));
+        assert!(matches!(string, Cow::Borrowed("text")));
+
+        let packed = pack(&(Bytes::from(&b"bl\x00ob"[..]), "te\0xt"));
+        let (bytes, string): (Bytes, Cow<str>) = unpack(&packed).unwrap();
+        assert!(matches!(&bytes.0, Cow::Owned(v) if v == b"bl\x00ob"));
+        assert!(matches!(&string, Cow::Owned(v) if v == "te\0xt"));
+
+        // elements unpacked one at a time, keeping the remaining input
+        let packed = pack(&(Bytes::from(&b"blob"[..]), Bytes::from(&b"\x00"[..])));
+        let (rest, first) = Element::unpack(&packed, TupleDepth::new().increment()).unwrap();
+        assert!(matches!(
+            first,
+            Element::Bytes(Bytes(Cow::Borrowed(b"blob")))
+        ));
+        let (rest, second) = Element::unpack(rest, TupleDepth::new().increment()).unwrap();
+        assert!(matches!(second, Element::Bytes(Bytes(Cow::Owned(_)))));
+        assert!(rest.is_empty());
+    }
+
+    #[test]
+    fn test_option() {
+        let packed = pack(&(Some(1), None::<i64>));
+        assert_eq!(packed, b"\x15\x01\x00");
+        assert_eq!(
+            unpack::<(Option<i64>, Option<i64>)>(&packed).unwrap(),
+            (Some(1), None)
+        );
+        // optional values inside a nested tuple
+        test_serde(
+            ((None::<i64>, Some("a".to_owned())),),
+            b"\x05\x00\xff\x02a\x00\x00",
+        );
+
+        // None sorts before any present value
+        assert!(pack(&(None::<i64>, None::<i64>)) < packed);
+        for smallest in [
+            pack(&Some(i64::MIN)),
+            pack(&Some("")),
+            pack(&Some(b"".as_ref())),
+        ] {
+            assert!(pack(&None::<i64>) < smallest);
+        }
+
+        // nested options collapse into None
+        assert_eq!(pack(&Some(None::<i64>)), pack(&None::<i64>));
+        assert_eq!(unpack::<Option<Option<i64>>>(&[NIL]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_unpack_str_borrow() {
+        let packed = pack(&("name",));
+        let (name,): (&str,) = unpack(&packed).unwrap();
+        assert_eq!(name, "name");
+        let packed = pack(&"name");
+        let name: &str = unpack(&packed).unwrap();
+        assert_eq!(name, "name");
+
+        // escaped nul bytes cannot be borrowed, Cow<str> copies them out instead
+        let packed = pack(&("na\0me",));
+        assert!(matches!(
+            unpack::<(&str,)>(&packed),
+            Err(PackError::Message(_))
+        ));
+        let (name,): (Cow<str>,) = unpack(&packed).unwrap();
+        assert!(matches!(&name, Cow::Owned(v) if v == "na\0me"));
+    }
+
+    #[cfg(all(feature = "serde", feature = "serde_json"))]
+    #[test]
+    fn test_element_serde() {
+        let element = Element::Tuple(vec![
+            Element::Nil,
+            Element::Bytes(b"\x00\xffab".as_slice().into()),
+            Element::String("hello".into()),
+            Element::Tuple(vec![Element::Int(-5), Element::Bool(true)]),
+            Element::Double(1.5),
+            Element::Versionstamp(Versionstamp::complete([1, 2, 3, 4, 5, 6, 7, 8, 9, 10], 2)),
+        ]);
+        let json = serde_json::to_string(&element).unwrap();
+        assert_eq!(
+            json,
+            r#"{"tuple":[{"nil":null},{"bytes":"AP9hYg=="},{"string":"hello"},{"tuple":[{"int":-5},{"bool":true}]},{"double":1.5},{"versionstamp":"AQIDBAUGBwgJCgAC"}]}"#
+        );
+        let decoded: Element = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, element);
+
+        assert!(serde_json::from_str::<Element>(r#"{"bytes":"AP9"}"#).is_err());
+        // non-zero bits past the last byte
+        assert!(serde_json::from_str::<Element>(r#"{"bytes":"AP9hYh=="}"#).is_err());
+        assert!(serde_json::from_str::<Element>(r#"{"bytes":"YWJ="}"#).is_err());
+        assert_eq!(
+            serde_json::from_str::<Element>(r#"{"bytes":"YWI="}"#).unwrap(),
+            Element::Bytes(b"ab".as_slice().into())
+        );
+        assert!(serde_json::from_str::<Element>(r#"{"int":1,"bool":true}"#).is_err());
+        assert!(serde_json::from_str::<Element>(r#"{"unknown":1}"#).is_err());
+    }
 }
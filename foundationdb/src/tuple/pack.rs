@@ -3,6 +3,7 @@ use memchr::memchr_iter;
 use std::convert::TryFrom;
 use std::io;
 use std::mem;
+use std::net::{Ipv4Addr, Ipv6Addr};
 
 #[derive(Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub enum VersionstampOffset {
@@ -875,6 +876,23 @@ impl<'de> TupleUnpack<'de> for Cow<'de, str> {
     }
 }
 
+/// Borrows the string straight from the input.
+///
+/// This only works when the packed string contains no nul byte, as those are escaped by the
+/// encoding and must be copied out. Such strings fail with [`PackError::Message`], unpack them
+/// as `Cow<str>` or `String` instead.
+impl<'de> TupleUnpack<'de> for &'de str {
+    fn unpack(input: &'de [u8], _tuple_depth: TupleDepth) -> PackResult<(&'de [u8], Self)> {
+        let input = parse_code(input, STRING)?;
+        match parse_string(input)? {
+            (input, Cow::Borrowed(v)) => Ok((input, v)),
+            (_, Cow::Owned(_)) => Err(PackError::Message(
+                "string contains escaped nul bytes, unpack as Cow<str> or String instead".into(),
+            )),
+        }
+    }
+}
+
 impl<'de> TupleUnpack<'de> for String {
     fn unpack(input: &[u8], _tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
         let input = parse_code(input, STRING)?;
@@ -883,6 +901,10 @@ impl<'de> TupleUnpack<'de> for String {
     }
 }
 
+/// `None` packs as the null element, which sorts before any other element, and `Some(v)` packs
+/// as `v` does.
+///
+/// Nested options cannot be told apart: `Some(None)` packs as `None` and unpacks back as `None`.
 impl<T> TuplePack for Option<T>
 where
     T: TuplePack,
@@ -1078,6 +1100,50 @@ impl<'de> TupleUnpack<'de> for Versionstamp {
     }
 }
 
+/// Packed as a byte string of the 4 address bytes in network order, so that addresses sort in
+/// numeric order.
+impl TuplePack for Ipv4Addr {
+    fn pack<W: io::Write>(
+        &self,
+        w: &mut W,
+        tuple_depth: TupleDepth,
+    ) -> io::Result<VersionstampOffset> {
+        Bytes::from(&self.octets()[..]).pack(w, tuple_depth)
+    }
+}
+
+impl<'de> TupleUnpack<'de> for Ipv4Addr {
+    fn unpack(input: &'de [u8], tuple_depth: TupleDepth) -> PackResult<(&'de [u8], Self)> {
+        let (input, bytes) = Bytes::unpack(input, tuple_depth)?;
+        let octets = <[u8; 4]>::try_from(bytes.as_ref()).map_err(|_| {
+            PackError::Message(format!("{} bytes is not an IPv4 address", bytes.len()).into())
+        })?;
+        Ok((input, Ipv4Addr::from(octets)))
+    }
+}
+
+/// Packed as a byte string of the 16 address bytes in network order, so that addresses sort in
+/// numeric order.
+impl TuplePack for Ipv6Addr {
+    fn pack<W: io::Write>(
+        &self,
+        w: &mut W,
+        tuple_depth: TupleDepth,
+    ) -> io::Result<VersionstampOffset> {
+        Bytes::from(&self.octets()[..]).pack(w, tuple_depth)
+    }
+}
+
+impl<'de> TupleUnpack<'de> for Ipv6Addr {
+    fn unpack(input: &'de [u8], tuple_depth: TupleDepth) -> PackResult<(&'de [u8], Self)> {
+        let (input, bytes) = Bytes::unpack(input, tuple_depth)?;
+        let octets = <[u8; 16]>::try_from(bytes.as_ref()).map_err(|_| {
+            PackError::Message(format!("{} bytes is not an IPv6 address", bytes.len()).into())
+        })?;
+        Ok((input, Ipv6Addr::from(octets)))
+    }
+}
+
 #[cfg(feature = "uuid")]
 mod pack_uuid {
     use super::*;
@@ -1104,3 +1170,48 @@ mod pack_uuid {
         }
     }
 }
+
+#[cfg(feature = "chrono")]
+mod pack_chrono {
+    use super::*;
+    use chrono::{DateTime, TimeZone, Utc};
+
+    const NANOS_PER_SEC: i128 = 1_000_000_000;
+
+    /// Packed as the integer number of nanoseconds since the Unix epoch, so that timestamps sort
+    /// in key order, before 1970 included.
+    ///
+    /// The count is an `i128`, wide enough for the whole `DateTime<Utc>` range; timestamps
+    /// between 1677 and 2262 fit in an `i64` and share its encoding. A leap second is folded
+    /// into the first second of the following minute.
+    impl TuplePack for DateTime<Utc> {
+        fn pack<W: io::Write>(
+            &self,
+            w: &mut W,
+            tuple_depth: TupleDepth,
+        ) -> io::Result<VersionstampOffset> {
+            let nanos = i128::from(self.timestamp()) * NANOS_PER_SEC
+                + i128::from(self.timestamp_subsec_nanos());
+            nanos.pack(w, tuple_depth)
+        }
+    }
+
+    impl<'de> TupleUnpack<'de> for DateTime<Utc> {
+        fn unpack(input: &[u8], tuple_depth: TupleDepth) -> PackResult<(&[u8], Self)> {
+            let (input, nanos) = i128::unpack(input, tuple_depth)?;
+            let datetime = i64::try_from(nanos.div_euclid(NANOS_PER_SEC))
+                .ok()
+                .and_then(|secs| {
+                    let subsec = nanos.rem_euclid(NANOS_PER_SEC) as u32;
+                    Utc.timestamp_opt(secs, subsec).single()
+                })
+                .ok_or_else(|| {
+                    PackError::Message(
+                        format!("{} nanoseconds is out of the DateTime range", nanos)
+                            .into_boxed_str(),
+                    )
+                })?;
+            Ok((input, datetime))
+        }
+    }
+}
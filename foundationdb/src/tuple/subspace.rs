@@ -7,7 +7,10 @@
 // copied, modified, or distributed except according to those terms.
 
 use super::*;
-use crate::{KeySelector, RangeOption, Transaction};
+use crate::directory::strinc;
+use crate::future::FdbValue;
+use crate::{FdbBindingError, FdbResult, KeySelector, RangeOption, Transaction};
+use futures::{Stream, StreamExt};
 use std::borrow::Cow;
 use std::hash::Hash;
 
@@ -88,6 +91,17 @@ impl Subspace {
         out
     }
 
+    /// Writes the key encoding the specified Tuple with the prefix of this Subspace prepended
+    /// into `output`, which is cleared first.
+    ///
+    /// This is the same key as `pack`, but reusing `output` across calls saves an allocation
+    /// per key when packing many of them.
+    pub fn pack_into<T: TuplePack>(&self, t: &T, output: &mut Vec<u8>) {
+        output.clear();
+        output.extend_from_slice(&self.prefix);
+        pack_into(t, output);
+    }
+
     /// Returns the key encoding the specified Tuple with the prefix of this Subspace
     /// prepended, with a versionstamp.
     pub fn pack_with_versionstamp<T: TuplePack>(&self, t: &T) -> Vec<u8> {
@@ -123,6 +137,13 @@ impl Subspace {
         key.starts_with(&self.prefix)
     }
 
+    /// `contains` returns true if the provided key belongs to this Subspace, its prefix included.
+    ///
+    /// This is an alias of [`Subspace::is_start_of`], a cheap check to run before `unpack`.
+    pub fn contains(&self, key: &[u8]) -> bool {
+        self.is_start_of(key)
+    }
+
     /// `range` returns first and last key of given Subspace
     pub fn range(&self) -> (Vec<u8>, Vec<u8>) {
         let mut begin = Vec::with_capacity(self.prefix.len() + 1);
@@ -135,6 +156,23 @@ impl Subspace {
 
         (begin, end)
     }
+
+    /// `range_starts_with` returns the range of all keys starting with the prefix of this
+    /// Subspace followed by the packed `t`, without creating an intermediate Subspace.
+    ///
+    /// This is the range to scan to list everything under a partial key. Unlike `range`, keys
+    /// equal to the packed prefix itself are included. The end of the range is the first key
+    /// that sorts after all those keys; it is `None`, meaning unbounded, when the packed prefix
+    /// is only made of `0xff` bytes.
+    pub fn range_starts_with<T: TuplePack>(&self, t: &T) -> (Vec<u8>, Option<Vec<u8>>) {
+        let begin = self.pack(t);
+        let end = strinc(begin.clone());
+        if end.is_empty() {
+            (begin, None)
+        } else {
+            (begin, Some(end))
+        }
+    }
 }
 
 impl<'a> From<&'a Subspace> for RangeOption<'static> {
@@ -150,10 +188,79 @@ impl<'a> From<&'a Subspace> for RangeOption<'static> {
 }
 
 impl Transaction {
+    /// Clears all the keys packed within `subspace`, as given by `Subspace::range`.
     pub fn clear_subspace_range(&self, subspace: &Subspace) {
         let (begin, end) = subspace.range();
         self.clear_range(&begin, &end)
     }
+
+    /// Clears all the keys whose tuple starts with the elements of `prefix`, including the key
+    /// packing `prefix` itself.
+    ///
+    /// Clearing `("u",)` clears `("u",)` and `("u", "x")` but leaves `("v",)` and `("u\0",)`
+    /// untouched, even though the packing of the latter starts with the packing of `("u",)`.
+    pub fn clear_starts_with<T: TuplePack>(&self, prefix: &T) {
+        let begin = pack(prefix);
+        let mut end = begin.clone();
+        end.push(0xff);
+        self.clear_range(&begin, &end)
+    }
+
+    /// Writes `defaults` if the given subspace does not contain any key yet.
+    ///
+    /// Returns `true` if the subspace was empty and the defaults were written, `false` otherwise.
+    /// The keys of `defaults` are used as is, they are expected to already be packed within the
+    /// subspace.
+    ///
+    /// The emptiness check is a regular (non-snapshot) read of the whole subspace, which adds it
+    /// as a read conflict range: if two transactions initialize the same subspace concurrently,
+    /// only the first to commit succeeds and the other one conflicts.
+    pub async fn init_subspace_if_empty(
+        &self,
+        subspace: &Subspace,
+        defaults: &[(Vec<u8>, Vec<u8>)],
+    ) -> FdbResult<bool> {
+        let opt = RangeOption {
+            limit: Some(1),
+            ..RangeOption::from(subspace)
+        };
+        if !self.get_range(&opt, 1, false).await?.is_empty() {
+            return Ok(false);
+        }
+
+        for (key, value) in defaults {
+            self.set(key, value);
+        }
+        Ok(true)
+    }
+}
+
+impl Subspace {
+    /// Streams all the key-value pairs of the subspace, unpacking each key relative to the
+    /// subspace prefix as a `T`.
+    ///
+    /// Keys that do not unpack as a `T` yield a `FdbBindingError::PackError`, the stream can be
+    /// resumed past them.
+    ///
+    /// # Arguments
+    ///
+    /// * `trx`: the transaction the range is read from
+    /// * `snapshot`: `true` if this is a [snapshot read](https://apple.github.io/foundationdb/api-c.html#snapshots)
+    pub fn range_stream<'a, T>(
+        &'a self,
+        trx: &'a Transaction,
+        snapshot: bool,
+    ) -> impl Stream<Item = Result<(T, FdbValue), FdbBindingError>> + Unpin + 'a
+    where
+        T: for<'de> TupleUnpack<'de>,
+    {
+        trx.get_ranges_keyvalues(RangeOption::from(self), snapshot)
+            .map(move |kv| {
+                let kv = kv?;
+                let key = self.unpack(kv.key()).map_err(FdbBindingError::PackError)?;
+                Ok((key, kv))
+            })
+    }
 }
 
 #[cfg(test)]
@@ -171,6 +278,38 @@ mod tests {
         assert_eq!(ss1.bytes(), ss2.bytes());
     }
 
+    #[test]
+    fn pack_into_reused_buffer() {
+        let ss0: Subspace = ("users", 1).into();
+        let mut buf = Vec::new();
+        let mut tuple_buf = Vec::new();
+        for i in 0..1000i64 {
+            let tup = (i, format!("name-{}", i), i % 2 == 0);
+
+            ss0.pack_into(&tup, &mut buf);
+            assert_eq!(buf, ss0.pack(&tup));
+
+            tuple_buf.clear();
+            tup.pack_into_vec(&mut tuple_buf);
+            assert_eq!(tuple_buf, pack(&tup));
+        }
+    }
+
+    #[test]
+    fn from_bytes_roundtrip() {
+        let ss0 = Subspace::from(("app", 42)).subspace(&"users");
+        let restored = Subspace::from_bytes(ss0.bytes());
+        assert_eq!(restored.bytes(), ss0.bytes());
+
+        let tup = (7, "alice");
+        assert_eq!(restored.pack(&tup), ss0.pack(&tup));
+        assert_eq!(restored.range(), ss0.range());
+        assert_eq!(
+            restored.unpack::<(i64, String)>(&ss0.pack(&tup)).unwrap(),
+            (7, "alice".to_string())
+        );
+    }
+
     #[test]
     fn pack_unpack() {
         let ss0: Subspace = 1.into();
@@ -283,6 +422,26 @@ mod tests {
         assert!(packed >= begin && packed <= end);
     }
 
+    #[test]
+    fn range_starts_with() {
+        let ss: Subspace = 1.into();
+        let (begin, end) = ss.range_starts_with(&("a", 2));
+        assert_eq!(begin, pack(&(1, "a", 2)));
+        let end = end.unwrap();
+        assert!(ss.pack(&("a", 2, 3)) < end);
+        assert!(ss.pack(&("a", 3)) >= end);
+
+        // trailing 0xff bytes are carried over
+        let ss = Subspace::from_bytes(vec![0x01, 0xff]);
+        let (begin, end) = ss.range_starts_with(&());
+        assert_eq!(begin, vec![0x01, 0xff]);
+        assert_eq!(end, Some(vec![0x02]));
+
+        // a prefix only made of 0xff bytes has no upper bound
+        let ss = Subspace::from_bytes(vec![0xff, 0xff]);
+        assert_eq!(ss.range_starts_with(&()), (vec![0xff, 0xff], None));
+    }
+
     #[test]
     fn equality() {
         let sub1 = Subspace::all().subspace(&"test");
@@ -14,6 +14,9 @@ mod common;
 fn test_atomic() {
     let _guard = unsafe { foundationdb::boot() };
     futures::executor::block_on(test_atomic_async()).expect("failed to run");
+    futures::executor::block_on(test_add_i64_async()).expect("failed to run");
+    futures::executor::block_on(test_counter_async()).expect("failed to run");
+    futures::executor::block_on(test_atomic_max_min_u64_async()).expect("failed to run");
 }
 
 async fn atomic_add(db: &Database, key: &[u8], value: i64) -> FdbResult<()> {
@@ -64,3 +67,88 @@ async fn test_atomic_async() -> FdbResult<()> {
     }
     Ok(())
 }
+
+async fn test_add_i64_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test-atomic-add-i64";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear(KEY);
+    trx.commit().await?;
+
+    let mut expected = 0i64;
+    for delta in [1, 41, -100, i64::from(u32::MAX), 7] {
+        let trx = db.create_trx()?;
+        trx.add_i64(KEY, delta);
+        trx.commit().await?;
+        expected += delta;
+    }
+
+    let trx = db.create_trx()?;
+    let value = trx.get(KEY, false).await?.expect("value should exists");
+    assert_eq!(value.len(), 8);
+    assert_eq!(byteorder::LE::read_i64(&value), expected);
+
+    Ok(())
+}
+
+async fn test_counter_async() -> FdbResult<()> {
+    let counter = Counter::new(&b"test-atomic-counter"[..]);
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear(counter.key());
+    assert_eq!(counter.read(&trx, false).await?, 0);
+    trx.commit().await?;
+
+    // two transactions incrementing concurrently do not conflict
+    let trx1 = db.create_trx()?;
+    let trx2 = db.create_trx()?;
+    counter.increment(&trx1, 40);
+    counter.increment(&trx2, 5);
+    counter.increment(&trx2, -3);
+    trx1.commit().await?;
+    trx2.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert_eq!(counter.read(&trx, false).await?, 42);
+    assert_eq!(counter.read(&trx, true).await?, 42);
+
+    Ok(())
+}
+
+async fn read_u64(db: &Database, key: &[u8]) -> FdbResult<u64> {
+    let trx = db.create_trx()?;
+    let value = trx.get(key, false).await?.expect("value should exists");
+    assert_eq!(value.len(), 8);
+    Ok(byteorder::LE::read_u64(&value))
+}
+
+async fn test_atomic_max_min_u64_async() -> FdbResult<()> {
+    const MAX_KEY: &[u8] = b"test-atomic-max-u64";
+    const MIN_KEY: &[u8] = b"test-atomic-min-u64";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear(MAX_KEY);
+    trx.clear(MIN_KEY);
+    trx.commit().await?;
+
+    // the stored value tracks the running maximum and minimum, a missing key takes the value
+    let (mut max, mut min) = (u64::MIN, u64::MAX);
+    for value in [50, 20, 10, 70, 60, 1 << 40, 1 << 63] {
+        let trx = db.create_trx()?;
+        trx.atomic_max_u64(MAX_KEY, value);
+        trx.atomic_min_u64(MIN_KEY, value);
+        trx.commit().await?;
+        max = max.max(value);
+        min = min.min(value);
+        assert_eq!(read_u64(&db, MAX_KEY).await?, max);
+        assert_eq!(read_u64(&db, MIN_KEY).await?, min);
+    }
+
+    Ok(())
+}
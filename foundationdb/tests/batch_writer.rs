@@ -0,0 +1,64 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use foundationdb::*;
+use foundationdb_macros::cfg_api_versions;
+
+mod common;
+
+#[test]
+fn test_batch_writer() {
+    let _guard = unsafe { foundationdb::boot() };
+    #[cfg(any(
+        feature = "fdb-6_2",
+        feature = "fdb-6_3",
+        feature = "fdb-7_0",
+        feature = "fdb-7_1",
+        feature = "fdb-7_3"
+    ))]
+    futures::executor::block_on(test_batch_writer_async()).expect("failed to run");
+}
+
+#[cfg_api_versions(min = 620)]
+async fn test_batch_writer_async() -> FdbResult<()> {
+    use rand::Rng;
+
+    const N: u32 = 100_000;
+    const PREFIX: &[u8] = b"test-batch-writer/";
+    let key = |i: u32| [PREFIX, &i.to_be_bytes()].concat();
+    let value = |i: u32| format!("value-{}", i).into_bytes();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(PREFIX);
+    trx.commit().await?;
+
+    // small transactions, so that the size threshold is reached many times
+    let mut writer = BatchWriter::new(&db)
+        .set_max_transaction_size(256 * 1024)
+        .set_max_count(20_000);
+    for i in 0..N {
+        writer.write(&key(i), &value(i)).await?;
+    }
+    assert!(writer.written() > 0);
+    assert_eq!(writer.finish().await?, N as u64);
+
+    let trx = db.create_trx()?;
+    let mut rng = rand::thread_rng();
+    for _ in 0..100 {
+        let i = rng.gen_range(0..N);
+        let read = trx.get(&key(i), false).await?;
+        assert_eq!(read.as_deref(), Some(value(i).as_slice()));
+    }
+
+    // nothing was written past the last key
+    let past_end = trx.get(&key(N), false).await?;
+    assert!(past_end.is_none());
+
+    Ok(())
+}
@@ -2,11 +2,42 @@ use foundationdb_macros::cfg_api_versions;
 
 mod common;
 
-#[cfg_api_versions(min = 730)]
 #[test]
-fn test_get_client_status() {
+fn test_database() {
     let _guard = unsafe { foundationdb::boot() };
+    #[cfg(feature = "fdb-7_3")]
     futures::executor::block_on(test_status_async()).expect("failed to run");
+    #[cfg(any(
+        feature = "fdb-6_1",
+        feature = "fdb-6_2",
+        feature = "fdb-6_3",
+        feature = "fdb-7_0",
+        feature = "fdb-7_1",
+        feature = "fdb-7_3"
+    ))]
+    {
+        futures::executor::block_on(test_from_connection_string_async()).expect("failed to run");
+        futures::executor::block_on(test_open_cluster_file_async()).expect("failed to run");
+        futures::executor::block_on(test_wait_for_healthy_async()).expect("failed to run");
+        futures::executor::block_on(test_default_transaction_timeout_async())
+            .expect("failed to run");
+    }
+    #[cfg(any(feature = "fdb-7_0", feature = "fdb-7_1", feature = "fdb-7_3"))]
+    futures::executor::block_on(test_reboot_unknown_worker_async()).expect("failed to run");
+}
+
+#[cfg_api_versions(min = 700)]
+async fn test_reboot_unknown_worker_async() -> foundationdb::FdbResult<()> {
+    let db = common::database().await?;
+
+    // no worker listens on this address, so nothing is rebooted
+    let rebooted = db.reboot_worker("127.0.0.1:1", false, 0).await?;
+    assert_eq!(rebooted, 0);
+
+    // forcing a recovery loses data, it is only type-checked here
+    let _force_recovery = |db: &foundationdb::Database| db.force_recovery_with_data_loss("dc0");
+
+    Ok(())
 }
 
 #[cfg_api_versions(min = 730)]
@@ -22,5 +53,116 @@ async fn test_status_async() -> foundationdb::FdbResult<()> {
         format!("{}", status)
     );
 
+    // the status is a JSON object reporting, among others, the health of the client
+    let status: serde_json::Value =
+        serde_json::from_str(status).expect("client status should be valid JSON");
+    assert!(status["Healthy"].is_boolean(), "{}", status);
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 610)]
+async fn test_open_cluster_file_async() -> foundationdb::FdbResult<()> {
+    use std::error::Error;
+    use std::path::Path;
+
+    let missing = Path::new("/nonexistent/fdb.cluster");
+    let err = foundationdb::Database::open_cluster_file(missing)
+        .err()
+        .expect("the cluster file does not exist");
+    assert_eq!(
+        err.source().map(|source| source.to_string()),
+        Some("cluster file not found: /nonexistent/fdb.cluster".to_string())
+    );
+
+    // a directory is refused before reaching the client
+    let err = foundationdb::Database::open_cluster_file(Path::new("/etc"))
+        .err()
+        .expect("a directory is not a cluster file");
+    assert_eq!(
+        err.source().map(|source| source.to_string()),
+        Some("cluster file /etc is not a file".to_string())
+    );
+
+    let db =
+        foundationdb::Database::open_cluster_file(Path::new(foundationdb::default_config_path()))
+            .expect("could not open the default cluster file");
+    let trx = db.create_trx()?;
+    trx.set(b"test-open-cluster-file", b"hello");
+    trx.commit().await?;
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 610)]
+async fn test_wait_for_healthy_async() -> foundationdb::FdbResult<()> {
+    use std::time::{Duration, Instant};
+
+    let db = common::database().await?;
+    let start = Instant::now();
+    db.wait_for_healthy(Duration::from_secs(10)).await?;
+    assert!(
+        start.elapsed() < Duration::from_secs(5),
+        "{:?}",
+        start.elapsed()
+    );
+
+    // nothing listens on this port
+    let unreachable = foundationdb::Database::from_connection_string("test:test@127.0.0.1:1")?;
+    let start = Instant::now();
+    let err = unreachable
+        .wait_for_healthy(Duration::from_secs(1))
+        .await
+        .expect_err("the cluster cannot be reached");
+    assert_eq!(err.code(), 1031);
+    let elapsed = start.elapsed();
+    assert!(
+        elapsed >= Duration::from_millis(900) && elapsed < Duration::from_secs(5),
+        "{:?}",
+        elapsed
+    );
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 610)]
+async fn test_from_connection_string_async() -> foundationdb::FdbResult<()> {
+    let cluster_file = std::fs::read_to_string(foundationdb::default_config_path())
+        .expect("could not read the default cluster file");
+    let db = foundationdb::Database::from_connection_string(cluster_file.trim())?;
+
+    let trx = db.create_trx()?;
+    trx.set(b"test-from-connection-string", b"hello");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let value = trx.get(b"test-from-connection-string", false).await?;
+    assert_eq!(value.as_deref(), Some(&b"hello"[..]));
+
+    // an interior NUL byte is reported as an invalid connection string
+    match foundationdb::Database::from_connection_string("test:test\0@127.0.0.1:4500") {
+        Err(err) => assert_eq!(err.code(), 2104),
+        Ok(_) => panic!("a connection string with a NUL byte should be rejected"),
+    }
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 610)]
+async fn test_default_transaction_timeout_async() -> foundationdb::FdbResult<()> {
+    let db = common::database().await?;
+    db.set_option(foundationdb::options::DatabaseOption::TransactionTimeout(
+        10,
+    ))?;
+
+    // transactions created afterward inherit the database default
+    let trx = db.create_trx()?;
+    std::thread::sleep(std::time::Duration::from_millis(100));
+    match trx.get(b"test-default-transaction-timeout", false).await {
+        // transaction_timed_out
+        Err(err) => assert_eq!(err.code(), 1031, "unexpected error: {}", err),
+        Ok(_) => panic!("transaction should have inherited the database timeout"),
+    }
+
     Ok(())
 }
@@ -7,7 +7,7 @@
 
 use foundationdb::directory::DirectoryLayer;
 
-use foundationdb::directory::Directory;
+use foundationdb::directory::{Directory, DirectoryError, DirectoryOutput};
 
 use foundationdb::*;
 
@@ -40,6 +40,68 @@ fn test_directory() {
         vec![String::from("1"), String::from("2")],
     ))
     .expect("failed to run");
+
+    futures::executor::block_on(test_move_to(&db, &directory)).expect("failed to run");
+    futures::executor::block_on(test_remove_if_exists(&db, &directory)).expect("failed to run");
+    futures::executor::block_on(test_partition(&db, &directory)).expect("failed to run");
+    futures::executor::block_on(test_list(&db, &directory)).expect("failed to run");
+    futures::executor::block_on(test_manual_prefix(&db, &directory)).expect("failed to run");
+}
+
+async fn test_manual_prefix(db: &Database, directory: &DirectoryLayer) -> FdbResult<()> {
+    const PREFIX: &[u8] = b"manual-prefix/";
+    let path = vec![String::from("manual")];
+
+    // the default directory layer lets the allocator pick prefixes
+    let trx = db.create_trx()?;
+    let err = directory
+        .create(&trx, &path, Some(PREFIX), None)
+        .await
+        .expect_err("manual prefixes are not allowed");
+    assert!(
+        matches!(err, DirectoryError::PrefixNotAllowed),
+        "unexpected error: {:?}",
+        err
+    );
+
+    let manual = DirectoryLayer::new(
+        tuple::Subspace::from_bytes(&b"\xfe"[..]),
+        tuple::Subspace::all(),
+        true,
+    );
+    let trx = db.create_trx()?;
+    let created = manual
+        .create(&trx, &path, Some(PREFIX), None)
+        .await
+        .expect("cannot create");
+    assert_eq!(created.bytes().unwrap(), PREFIX);
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    let opened = directory
+        .open(&trx, &path, None)
+        .await
+        .expect("cannot open");
+    assert_eq!(opened.bytes().unwrap(), PREFIX);
+    let key = opened.pack(&("user", 42)).unwrap();
+    assert!(key.starts_with(PREFIX));
+    let unpacked: (String, i64) = opened.unpack(&key).unwrap().unwrap();
+    assert_eq!(unpacked, ("user".to_string(), 42));
+
+    // the prefix, and any prefix within it, are now in use
+    for prefix in [PREFIX, &b"manual-prefix/sub"[..]] {
+        let err = manual
+            .create(&trx, &[String::from("other")], Some(prefix), None)
+            .await
+            .expect_err("the prefix is in use");
+        assert!(
+            matches!(err, DirectoryError::DirectoryPrefixInUse),
+            "unexpected error: {:?}",
+            err
+        );
+    }
+
+    Ok(())
 }
 
 async fn test_create_then_open_then_delete(
@@ -76,3 +138,157 @@ async fn test_create_then_open_then_delete(
     // removing folder
     Ok(())
 }
+
+async fn test_move_to(db: &Database, directory: &DirectoryLayer) -> FdbResult<()> {
+    let old_path = vec![String::from("move"), String::from("old")];
+    let new_path = vec![String::from("move"), String::from("new")];
+
+    let trx = db.create_trx()?;
+    let created = directory
+        .create(&trx, &old_path, None, None)
+        .await
+        .expect("cannot create");
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    let moved = directory
+        .move_to(&trx, &old_path, &new_path)
+        .await
+        .expect("cannot move");
+    assert_eq!(moved.get_path(), new_path.as_slice());
+    assert_eq!(created.bytes().unwrap(), moved.bytes().unwrap());
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    assert!(!directory.exists(&trx, &old_path).await.unwrap());
+    assert!(directory.exists(&trx, &new_path).await.unwrap());
+
+    // moving a directory under itself is rejected
+    let mut sub_path = new_path.clone();
+    sub_path.push(String::from("child"));
+    let err = directory
+        .move_to(&trx, &new_path, &sub_path)
+        .await
+        .expect_err("moved a directory under itself");
+    assert!(
+        matches!(err, DirectoryError::CannotMoveBetweenSubdirectory),
+        "unexpected error: {:?}",
+        err
+    );
+
+    Ok(())
+}
+
+async fn test_remove_if_exists(db: &Database, directory: &DirectoryLayer) -> FdbResult<()> {
+    let path = vec![String::from("remove_if_exists")];
+
+    let trx = db.create_trx()?;
+    directory
+        .create(&trx, &path, None, None)
+        .await
+        .expect("cannot create");
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    assert!(directory.remove_if_exists(&trx, &path).await.unwrap());
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    assert!(!directory.remove_if_exists(&trx, &path).await.unwrap());
+    assert!(matches!(
+        directory.remove(&trx, &path).await,
+        Err(DirectoryError::DirectoryDoesNotExists)
+    ));
+
+    Ok(())
+}
+
+async fn test_partition(db: &Database, directory: &DirectoryLayer) -> FdbResult<()> {
+    let path = vec![String::from("partition")];
+
+    let trx = db.create_trx()?;
+    let partition = directory
+        .create_or_open(&trx, &path, None, Some(b"partition"))
+        .await
+        .expect("cannot create partition");
+    assert!(matches!(partition, DirectoryOutput::DirectoryPartition(_)));
+    assert_eq!(partition.get_layer(), b"partition");
+    // the root of a partition cannot be used to store keys
+    assert!(matches!(
+        partition.pack(&"key"),
+        Err(DirectoryError::CannotPackDirectoryPartition)
+    ));
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    let partition = directory
+        .open(&trx, &path, Some(b"partition"))
+        .await
+        .expect("cannot reopen partition");
+    assert!(matches!(partition, DirectoryOutput::DirectoryPartition(_)));
+
+    let child = partition
+        .create(&trx, &[String::from("child")], None, None)
+        .await
+        .expect("cannot create child in partition");
+    assert!(matches!(child, DirectoryOutput::DirectorySubspace(_)));
+    assert_eq!(
+        child.get_path(),
+        &[String::from("partition"), String::from("child")]
+    );
+    trx.commit().await.expect("cannot commit");
+
+    // the child is reachable from the root directory layer as well
+    let trx = db.create_trx()?;
+    let reopened = directory
+        .open(
+            &trx,
+            &[String::from("partition"), String::from("child")],
+            None,
+        )
+        .await
+        .expect("cannot open child from root");
+    assert_eq!(child.bytes().unwrap(), reopened.bytes().unwrap());
+    assert_eq!(
+        partition.list(&trx, &[]).await.unwrap(),
+        vec![String::from("child")]
+    );
+
+    Ok(())
+}
+
+async fn test_list(db: &Database, directory: &DirectoryLayer) -> FdbResult<()> {
+    let path = |names: &[&str]| {
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+    };
+
+    let trx = db.create_trx()?;
+    // created out of order, listed sorted
+    for names in [&["list", "c"][..], &["list", "b"][..]] {
+        directory
+            .create(&trx, &path(names), None, None)
+            .await
+            .expect("cannot create");
+    }
+    trx.commit().await.expect("cannot commit");
+
+    let trx = db.create_trx()?;
+    assert_eq!(
+        directory.list(&trx, &path(&["list"])).await.unwrap(),
+        path(&["b", "c"])
+    );
+    assert!(directory
+        .list(&trx, &path(&["list", "b"]))
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(matches!(
+        directory.list(&trx, &path(&["list", "missing"])).await,
+        Err(DirectoryError::PathDoesNotExists)
+    ));
+
+    Ok(())
+}
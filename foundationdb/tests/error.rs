@@ -1,4 +1,5 @@
-use foundationdb::FdbBindingError;
+use foundationdb::{FdbBindingError, FdbError};
+use std::io;
 
 #[test]
 // This test is here because I'm always creating infinite recursion on Display and Debug impl 🤦
@@ -9,3 +10,85 @@ fn test_debug_display_trait() {
     println!("{}", error);
     println!("{:?}", error);
 }
+
+#[test]
+fn test_io_error_conversion() {
+    fn kind_and_code(code: i32) -> (io::ErrorKind, Option<i32>) {
+        let err = io::Error::from(FdbError::from_code(code));
+        let inner_code = err
+            .get_ref()
+            .and_then(|inner| inner.downcast_ref::<FdbError>())
+            .map(|inner| inner.code());
+        (err.kind(), inner_code)
+    }
+
+    // not_committed
+    assert_eq!(
+        kind_and_code(1020),
+        (io::ErrorKind::Interrupted, Some(1020))
+    );
+    // tenant_not_found
+    assert_eq!(kind_and_code(2131), (io::ErrorKind::NotFound, Some(2131)));
+    // transaction_too_large
+    assert_eq!(kind_and_code(2101), (io::ErrorKind::Other, Some(2101)));
+}
+
+#[test]
+fn test_fdb_error_description() {
+    // not_committed
+    let error = FdbError::from_code(1020);
+    assert!(error.to_string().contains("conflict"));
+    let debug = format!("{:?}", error);
+    assert!(debug.contains("1020"));
+    assert!(debug.contains("conflict"));
+}
+
+#[test]
+fn test_binding_error_source() {
+    use std::error::Error;
+
+    // what error reporters such as anyhow display with `{:#}`
+    fn chain(err: &dyn Error) -> Vec<String> {
+        let mut chain = vec![err.to_string()];
+        let mut source = err.source();
+        while let Some(err) = source {
+            chain.push(err.to_string());
+            source = err.source();
+        }
+        chain
+    }
+
+    // not_committed
+    let error = FdbError::from_code(1020);
+    assert!(error.to_string().contains("(1020)"));
+    assert!(error.source().is_none());
+
+    let wrapped: Box<dyn Error + Send + Sync> = Box::new(FdbBindingError::from(error));
+    let chain = chain(wrapped.as_ref());
+    assert_eq!(chain.len(), 2);
+    // the wrapped error is only displayed once, as the source
+    assert!(!chain[0].contains("conflict"), "{:?}", chain);
+    assert_eq!(chain[1], error.to_string());
+    assert!(chain[1].contains("conflict"));
+
+    let source = wrapped.source().expect("the FdbError should be the source");
+    assert_eq!(
+        source.downcast_ref::<FdbError>().map(|e| e.code()),
+        Some(1020)
+    );
+
+    let custom =
+        FdbBindingError::new_custom_error(Box::new(io::Error::new(io::ErrorKind::Other, "custom")));
+    assert_eq!(
+        custom.source().map(|e| e.to_string()).as_deref(),
+        Some("custom")
+    );
+    assert_eq!(custom.to_string(), "custom error");
+    assert!(FdbBindingError::ReferenceToTransactionKept
+        .source()
+        .is_none());
+    assert_eq!(
+        FdbBindingError::ReferenceToTransactionKept.to_string(),
+        "Reference to transaction kept"
+    );
+}
@@ -17,18 +17,56 @@ mod common;
 fn test_get() {
     let _guard = unsafe { foundationdb::boot() };
     futures::executor::block_on(test_set_get_async()).expect("failed to run");
+    futures::executor::block_on(test_set_all_async()).expect("failed to run");
     futures::executor::block_on(test_get_multi_async()).expect("failed to run");
+    futures::executor::block_on(test_get_multi_positional_async()).expect("failed to run");
     futures::executor::block_on(test_set_conflict_async()).expect("failed to run");
     futures::executor::block_on(test_set_conflict_snapshot_async()).expect("failed to run");
+    futures::executor::block_on(test_snapshot_view_async()).expect("failed to run");
+    futures::executor::block_on(test_map_result_async()).expect("failed to run");
+    futures::executor::block_on(test_values_as_bytes_async()).expect("failed to run");
+    futures::executor::block_on(test_on_error_async()).expect("failed to run");
+    futures::executor::block_on(test_run_boxed_async()).expect("failed to run");
+    futures::executor::block_on(test_run_with_metrics_async()).expect("failed to run");
+    futures::executor::block_on(test_reset_async()).expect("failed to run");
+    futures::executor::block_on(test_commit_and_get_version_async()).expect("failed to run");
+    futures::executor::block_on(test_slice_comparisons_async()).expect("failed to run");
+    futures::executor::block_on(test_slice_split_at_async()).expect("failed to run");
+    futures::executor::block_on(test_resolve_key_async()).expect("failed to run");
+    futures::executor::block_on(test_get_at_selector_async()).expect("failed to run");
+    futures::executor::block_on(test_get_or_async()).expect("failed to run");
+    futures::executor::block_on(test_rename_key_async()).expect("failed to run");
+    futures::executor::block_on(test_get_committed_async()).expect("failed to run");
     futures::executor::block_on(test_transact_async()).expect("failed to run");
     futures::executor::block_on(test_transact_limit()).expect("failed to run");
     futures::executor::block_on(test_transact_timeout()).expect("failed to run");
+    futures::executor::block_on(test_transaction_options_timeout()).expect("failed to run");
     futures::executor::block_on(test_versionstamp_async()).expect("failed to run");
+    #[cfg(any(
+        feature = "fdb-5_2",
+        feature = "fdb-6_0",
+        feature = "fdb-6_1",
+        feature = "fdb-6_2",
+        feature = "fdb-6_3",
+        feature = "fdb-7_0",
+        feature = "fdb-7_1",
+        feature = "fdb-7_3"
+    ))]
+    futures::executor::block_on(test_set_versionstamped_value_async()).expect("failed to run");
     futures::executor::block_on(test_read_version_async()).expect("failed to run");
     futures::executor::block_on(test_set_read_version_async()).expect("failed to run");
     futures::executor::block_on(test_get_addresses_for_key_async()).expect("failed to run");
     futures::executor::block_on(test_set_raw_option_async()).expect("failed to run");
+    futures::executor::block_on(test_options_set_async()).expect("failed to run");
     futures::executor::block_on(test_fails_to_set_unknown_raw_option()).expect("failed to run");
+    futures::executor::block_on(test_lock_aware_async()).expect("failed to run");
+    #[cfg(any(
+        feature = "fdb-6_3",
+        feature = "fdb-7_0",
+        feature = "fdb-7_1",
+        feature = "fdb-7_3"
+    ))]
+    futures::executor::block_on(test_get_conflicting_keys_async()).expect("failed to run");
     #[cfg(any(
         feature = "fdb-7_1",
         feature = "fdb-7_0",
@@ -37,6 +75,14 @@ fn test_get() {
         feature = "fdb-6_1"
     ))]
     futures::executor::block_on(test_metadata_version()).expect("failed to run");
+    #[cfg(any(
+        feature = "fdb-7_3",
+        feature = "fdb-7_1",
+        feature = "fdb-7_0",
+        feature = "fdb-6_3",
+        feature = "fdb-6_2"
+    ))]
+    futures::executor::block_on(test_get_approximate_size()).expect("failed to run");
 }
 
 async fn test_set_get_async() -> FdbResult<()> {
@@ -59,6 +105,29 @@ async fn test_set_get_async() -> FdbResult<()> {
     Ok(())
 }
 
+async fn test_set_all_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let fixtures: std::collections::HashMap<String, Vec<u8>> = (0..10)
+        .map(|i| (format!("test_set_all/{}", i), vec![i; 3]))
+        .collect();
+
+    let trx = db.create_trx()?;
+    trx.set_all(&fixtures);
+    trx.set_all([("test_set_all/extra", "value")]);
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    for (key, value) in &fixtures {
+        let read = trx.get(key.as_bytes(), false).await?;
+        assert_eq!(read.as_deref(), Some(value.as_slice()));
+    }
+    let extra = trx.get(b"test_set_all/extra", false).await?;
+    assert_eq!(extra.as_deref(), Some(b"value".as_ref()));
+
+    Ok(())
+}
+
 async fn test_get_multi_async() -> FdbResult<()> {
     let db = common::database().await?;
 
@@ -69,6 +138,32 @@ async fn test_get_multi_async() -> FdbResult<()> {
     Ok(())
 }
 
+async fn test_get_multi_positional_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(b"test_get_multi_a", b"a");
+    trx.clear(b"test_get_multi_b");
+    trx.set(b"test_get_multi_c", b"c");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let keys: &[&[u8]] = &[
+        b"test_get_multi_c",
+        b"test_get_multi_b",
+        b"test_get_multi_a",
+    ];
+    let results = trx.get_multi(keys.iter().copied(), false).await?;
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0].as_deref(), Some(b"c".as_ref()));
+    assert!(results[1].is_none());
+    assert_eq!(results[2].as_deref(), Some(b"a".as_ref()));
+
+    assert!(trx.get_multi(std::iter::empty(), false).await?.is_empty());
+
+    Ok(())
+}
+
 async fn test_set_conflict_async() -> FdbResult<()> {
     let key = b"test_set_conflict";
     let db = common::database().await?;
@@ -92,11 +187,11 @@ async fn test_set_conflict_async() -> FdbResult<()> {
     );
     assert_eq!(
         format!("{}", err),
-        "Transaction not committed due to conflict with another transaction"
+        "Transaction not committed due to conflict with another transaction (1020)"
     );
     assert_eq!(
         format!("{:?}", err),
-        "TransactionCommitError(Transaction not committed due to conflict with another transaction)"
+        "TransactionCommitError(Transaction not committed due to conflict with another transaction (1020))"
     );
     assert!(err.is_retryable());
     assert!(err.is_retryable_not_committed());
@@ -127,6 +222,527 @@ async fn test_set_conflict_snapshot_async() -> FdbResult<()> {
     Ok(())
 }
 
+async fn test_snapshot_view_async() -> FdbResult<()> {
+    let key = b"test_snapshot_view";
+    let db = common::database().await?;
+
+    let trx1 = db.create_trx()?;
+    let trx2 = db.create_trx()?;
+
+    // reads through the snapshot view do not set conflict ranges either
+    let snapshot = trx2.snapshot();
+    let _ = snapshot.get(key).await?;
+    let _ = snapshot
+        .get_range(
+            &RangeOption::from((&key[..], &b"test_snapshot_viex"[..])),
+            1,
+        )
+        .await?;
+
+    trx1.set(key, common::random_str(10).as_bytes());
+    trx1.commit().await?;
+
+    trx2.set(key, common::random_str(10).as_bytes());
+    trx2.commit().await?;
+
+    Ok(())
+}
+
+async fn test_resolve_key_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(b"test_resolve_key/", b"test_resolve_key0");
+    for key in [
+        &b"test_resolve_key/a"[..],
+        b"test_resolve_key/c",
+        b"test_resolve_key/e",
+    ] {
+        trx.set(key, b"");
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    // first key >= b
+    let key = trx
+        .resolve_key(b"test_resolve_key/b", 1, false, false)
+        .await?;
+    assert_eq!(key, b"test_resolve_key/c");
+    // first key > c
+    let key = trx
+        .resolve_key(b"test_resolve_key/c", 1, true, false)
+        .await?;
+    assert_eq!(key, b"test_resolve_key/e");
+    // last key < c
+    let key = trx
+        .resolve_key(b"test_resolve_key/c", 0, false, true)
+        .await?;
+    assert_eq!(key, b"test_resolve_key/a");
+    // same as the equivalent key selector
+    let key = trx
+        .snapshot()
+        .resolve_key(b"test_resolve_key/b", 2, false)
+        .await?;
+    let expected = trx
+        .get_key(
+            &KeySelector::new(b"test_resolve_key/b".as_slice().into(), false, 2),
+            true,
+        )
+        .await?;
+    assert_eq!(key, expected);
+    assert_eq!(key, b"test_resolve_key/e");
+
+    // shifting a selector moves it by that many keys
+    let selector = KeySelector::first_greater_or_equal(&b"test_resolve_key/a"[..]);
+    let key = trx.get_key(&(selector.clone() + 2), false).await?;
+    assert_eq!(key, b"test_resolve_key/e");
+    let key = trx.get_key(&(selector + 2 - 1), false).await?;
+    assert_eq!(key, b"test_resolve_key/c");
+    let selector = KeySelector::first_greater_than(&b"test_resolve_key/a"[..]) + 1;
+    assert!(selector.or_equal());
+    assert_eq!(selector.offset(), 2);
+    let key = trx.get_key(&selector, false).await?;
+    assert_eq!(key, b"test_resolve_key/e");
+
+    Ok(())
+}
+
+async fn test_get_at_selector_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(b"test_get_at_selector/", b"test_get_at_selector0");
+    trx.set(b"test_get_at_selector/a", b"value-a");
+    trx.set(b"test_get_at_selector/c", b"value-c");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    // first key >= b
+    let (key, value) = trx
+        .get_at_selector(
+            KeySelector::first_greater_or_equal(&b"test_get_at_selector/b"[..]),
+            false,
+        )
+        .await?
+        .expect("a key should follow b");
+    assert_eq!(key, b"test_get_at_selector/c");
+    assert_eq!(value, b"value-c");
+
+    // the selector's own key is resolved to itself
+    let (key, value) = trx
+        .get_at_selector(
+            KeySelector::first_greater_or_equal(&b"test_get_at_selector/a"[..]),
+            false,
+        )
+        .await?
+        .expect("a is set");
+    assert_eq!(key, b"test_get_at_selector/a");
+    assert_eq!(value, b"value-a");
+
+    // last key < c
+    let (key, _) = trx
+        .get_at_selector(
+            KeySelector::last_less_than(&b"test_get_at_selector/c"[..]),
+            false,
+        )
+        .await?
+        .expect("a precedes c");
+    assert_eq!(key, b"test_get_at_selector/a");
+
+    // past the end of the keyspace
+    let resolved = trx
+        .get_at_selector(KeySelector::first_greater_or_equal(&b"\xff"[..]), false)
+        .await?;
+    assert!(resolved.is_none());
+
+    Ok(())
+}
+
+async fn test_get_or_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_get_or";
+    const MISSING: &[u8] = b"test_get_or_missing";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"stored");
+    trx.clear(MISSING);
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert_eq!(trx.get_or(KEY, b"default", false).await?, b"stored");
+    assert_eq!(trx.get_or(MISSING, b"default", false).await?, b"default");
+
+    // the fallback is only computed for missing keys
+    let calls = Arc::new(AtomicUsize::new(0));
+    let fallback = |calls: Arc<AtomicUsize>| {
+        move || {
+            calls.fetch_add(1, Ordering::SeqCst);
+            b"computed".to_vec()
+        }
+    };
+    let value = trx.get_or_else(KEY, true, fallback(calls.clone())).await?;
+    assert_eq!(value, b"stored");
+    let value = trx
+        .get_or_else(MISSING, true, fallback(calls.clone()))
+        .await?;
+    assert_eq!(value, b"computed");
+    assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+    Ok(())
+}
+
+async fn test_rename_key_async() -> FdbResult<()> {
+    const OLD: &[u8] = b"test_rename_key/old";
+    const NEW: &[u8] = b"test_rename_key/new";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(b"test_rename_key/", b"test_rename_key0");
+    trx.set(OLD, b"value");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert!(trx.rename_key(OLD, NEW).await?);
+    // a missing key is not renamed
+    assert!(!trx.rename_key(b"test_rename_key/missing", OLD).await?);
+    // renaming a key to itself keeps it
+    assert!(trx.rename_key(NEW, NEW).await?);
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert!(trx.get(OLD, false).await?.is_none());
+    assert_eq!(trx.get(NEW, false).await?.as_deref(), Some(&b"value"[..]));
+
+    Ok(())
+}
+
+async fn test_get_committed_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_get_committed/key";
+    const ABSENT: &[u8] = b"test_get_committed/absent";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(b"test_get_committed/", b"test_get_committed0");
+    trx.set(KEY, b"old");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"new");
+    trx.set(ABSENT, b"new");
+
+    let committed = trx.get_committed(KEY, false).await?;
+    assert_eq!(committed.as_deref(), Some(&b"old"[..]));
+    assert!(trx.get_committed(ABSENT, true).await?.is_none());
+
+    // the other reads still see the staged writes
+    assert_eq!(trx.get(KEY, false).await?.as_deref(), Some(&b"new"[..]));
+    assert_eq!(trx.get(KEY, true).await?.as_deref(), Some(&b"new"[..]));
+    assert_eq!(trx.get(ABSENT, true).await?.as_deref(), Some(&b"new"[..]));
+
+    // a non-snapshot committed read conflicts like a regular one
+    let trx = db.create_trx()?;
+    trx.get_committed(KEY, false).await?;
+    trx.set(b"test_get_committed/other", b"");
+    let concurrent = db.create_trx()?;
+    concurrent.set(KEY, b"concurrent");
+    concurrent.commit().await?;
+    let err = trx.commit().await.expect_err("the read of KEY conflicts");
+    assert_eq!(err.code(), 1020);
+
+    Ok(())
+}
+
+async fn test_map_result_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_map_result";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"value");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert!(trx.get(KEY, false).map_ok(|v| v.is_some()).await?);
+    assert!(
+        !trx.get(b"test_map_result_missing", false)
+            .map_ok(|v| v.is_some())
+            .await?
+    );
+
+    // on_error maps the resolved future back to the transaction
+    let trx = trx.on_error(FdbError::from_code(1020)).await?;
+    assert!(trx.get(KEY, false).map_ok(|v| v.is_some()).await?);
+
+    Ok(())
+}
+
+fn as_bytes(bytes: &impl AsRef<[u8]>) -> &[u8] {
+    bytes.as_ref()
+}
+
+async fn test_values_as_bytes_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_values_as_bytes";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"value");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let value = trx.get(KEY, false).await?.expect("value should be set");
+    assert_eq!(as_bytes(&value), b"value");
+    assert_eq!(&value[..], b"value");
+
+    let key = trx
+        .get_key(&KeySelector::first_greater_or_equal(KEY), false)
+        .await?;
+    assert_eq!(as_bytes(&key), KEY);
+    assert_eq!(&key[..], KEY);
+
+    Ok(())
+}
+
+async fn test_on_error_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_on_error";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear(KEY);
+    trx.commit().await?;
+
+    // a retryable error resets the transaction, dropping its pending writes
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"first attempt");
+    let trx = trx.on_error(FdbError::from_code(1020)).await?;
+    assert!(trx.get(KEY, false).await?.is_none());
+    trx.set(KEY, b"second attempt");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert_eq!(
+        trx.get(KEY, false).await?.as_deref(),
+        Some(&b"second attempt"[..])
+    );
+
+    // the transaction that failed to commit is given back with the error
+    let trx = db.create_trx()?;
+    trx.get(KEY, false).await?;
+    make_dirty(&db, KEY).await?;
+    trx.set(KEY, b"conflicting attempt");
+    let (err, trx) = trx
+        .commit()
+        .await
+        .expect_err("the transaction should conflict")
+        .into_parts();
+    assert_eq!(err.code(), 1020);
+    let trx = trx.on_error(err).await?;
+    trx.set(KEY, b"third attempt");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert_eq!(
+        trx.get(KEY, false).await?.as_deref(),
+        Some(&b"third attempt"[..])
+    );
+
+    // a non retryable error is given back
+    match trx.on_error(FdbError::from_code(2101)).await {
+        Err(err) => assert_eq!(err.code(), 2101),
+        Ok(_) => panic!("transaction_too_large should not be retryable"),
+    }
+
+    Ok(())
+}
+
+async fn test_run_boxed_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_run_boxed";
+    let db = common::database().await?;
+
+    type Body = Box<
+        dyn for<'t> Fn(&'t Transaction) -> BoxFuture<'t, FdbResult<Option<Vec<u8>>>> + Send + Sync,
+    >;
+    let bodies: Vec<Body> = vec![
+        Box::new(|trx| {
+            trx.set(KEY, b"boxed");
+            ready(Ok(None)).boxed()
+        }),
+        Box::new(|trx| {
+            trx.get(KEY, false)
+                .map_ok(|value| value.map(|value| value.to_vec()))
+                .boxed()
+        }),
+    ];
+
+    let mut results = Vec::new();
+    for body in bodies {
+        results.push(db.run_boxed(body).await?);
+    }
+    assert_eq!(results, vec![None, Some(b"boxed".to_vec())]);
+
+    Ok(())
+}
+
+#[derive(Default)]
+struct CountingMetrics {
+    retries: std::sync::Mutex<Vec<i32>>,
+    commits: AtomicUsize,
+    give_ups: std::sync::Mutex<Vec<i32>>,
+}
+
+impl TransactionMetrics for CountingMetrics {
+    fn on_retry(&self, code: i32) {
+        self.retries.lock().unwrap().push(code);
+    }
+    fn on_commit(&self, _latency: std::time::Duration) {
+        self.commits.fetch_add(1, Ordering::SeqCst);
+    }
+    fn on_give_up(&self, code: i32) {
+        self.give_ups.lock().unwrap().push(code);
+    }
+}
+
+async fn test_run_with_metrics_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let metrics = CountingMetrics::default();
+    let attempts = AtomicUsize::new(0);
+    db.run_with_metrics(
+        |trx, _maybe_committed| {
+            let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+            async move {
+                if attempt < 2 {
+                    // not_committed
+                    return Err(FdbError::from_code(1020).into());
+                }
+                trx.set(b"test_run_with_metrics", b"done");
+                Ok(())
+            }
+        },
+        &metrics,
+    )
+    .await
+    .expect("failed to run");
+    assert_eq!(*metrics.retries.lock().unwrap(), vec![1020, 1020]);
+    assert_eq!(metrics.commits.load(Ordering::SeqCst), 1);
+    assert!(metrics.give_ups.lock().unwrap().is_empty());
+
+    let metrics = CountingMetrics::default();
+    let result = db
+        .run_with_metrics(
+            |_trx, _maybe_committed| async move {
+                // transaction_too_large
+                Err::<(), _>(FdbError::from_code(2101).into())
+            },
+            &metrics,
+        )
+        .await;
+    assert!(result.is_err());
+    assert!(metrics.retries.lock().unwrap().is_empty());
+    assert_eq!(*metrics.give_ups.lock().unwrap(), vec![2101]);
+
+    Ok(())
+}
+
+async fn test_reset_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_reset";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear(KEY);
+    trx.commit().await?;
+
+    let mut trx = db.create_trx()?;
+    trx.set(KEY, b"discarded");
+    trx.reset();
+    assert!(trx.get(KEY, false).await?.is_none());
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert!(trx.get(KEY, false).await?.is_none());
+
+    Ok(())
+}
+
+async fn test_commit_and_get_version_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let read_version = db.create_trx()?.get_read_version().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(b"test_commit_and_get_version", b"");
+    let version = trx.commit_and_get_version().await?;
+    assert!(version > 0);
+    assert!(version > read_version);
+
+    // read-only transactions have no committed version
+    let trx = db.create_trx()?;
+    let _ = trx.get(b"test_commit_and_get_version", false).await?;
+    match trx.commit_and_get_version().await {
+        Err(err) => assert_eq!(err.code(), 2000),
+        Ok(version) => panic!("read-only commit returned version {}", version),
+    }
+
+    Ok(())
+}
+
+async fn test_slice_comparisons_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(b"test_slice_comparisons_a", b"hello");
+    trx.set(b"test_slice_comparisons_b", b"world");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let a = trx
+        .get(b"test_slice_comparisons_a", false)
+        .await?
+        .expect("value should be set");
+    let b = trx
+        .get(b"test_slice_comparisons_b", false)
+        .await?
+        .expect("value should be set");
+
+    assert_eq!(a, b"hello");
+    assert_eq!(a, *b"hello");
+    assert_eq!(a, &b"hello"[..]);
+    assert_eq!(a, b"hello".to_vec());
+    assert_eq!(a, "hello");
+    assert_eq!(a, *"hello");
+    assert!(&a == "hello");
+    assert_eq!(a.to_vec(), b"hello".to_vec());
+    assert_ne!(a, b);
+    assert!(a < b);
+    assert!(a < b"world"[..]);
+
+    Ok(())
+}
+
+async fn test_slice_split_at_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_slice_split_at";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"HDR1payload");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let value = trx.get(KEY, false).await?.expect("value should be set");
+
+    let (header, payload) = value.split_at(4);
+    assert_eq!(header, b"HDR1");
+    assert_eq!(payload, b"payload");
+    assert_eq!(value.split_at(0), (&b""[..], &b"HDR1payload"[..]));
+    assert_eq!(
+        value.split_at_checked(11),
+        Some((&b"HDR1payload"[..], &b""[..]))
+    );
+    assert_eq!(value.split_at_checked(12), None);
+
+    Ok(())
+}
+
 // Makes the key dirty. It will abort transactions which performs non-snapshot read on the `key`.
 async fn make_dirty(db: &Database, key: &[u8]) -> FdbResult<()> {
     let trx = db.create_trx()?;
@@ -260,6 +876,36 @@ async fn test_transact_timeout() -> FdbResult<()> {
     Ok(())
 }
 
+async fn test_transaction_options_timeout() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_transaction_options_timeout";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    TransactionOptions::new()
+        .timeout(std::time::Duration::from_millis(10))
+        .retry_limit(3)
+        .apply(&trx)?;
+
+    // let the timeout elapse before doing any work
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    match trx.get(KEY, false).await {
+        Err(err) => assert_eq!(err.code(), 1031, "unexpected error: {}", err),
+        Ok(_) => panic!("transaction should have timed out"),
+    }
+
+    let options = TransactionOptions::new().timeout(std::time::Duration::from_millis(10));
+    let trx = db.create_trx_with_options(&options)?;
+    std::thread::sleep(std::time::Duration::from_millis(100));
+
+    match trx.get(KEY, false).await {
+        Err(err) => assert_eq!(err.code(), 1031, "unexpected error: {}", err),
+        Ok(_) => panic!("transaction should have timed out"),
+    }
+
+    Ok(())
+}
+
 async fn test_versionstamp_async() -> FdbResult<()> {
     const KEY: &[u8] = b"test_versionstamp";
     let db = common::database().await?;
@@ -273,6 +919,33 @@ async fn test_versionstamp_async() -> FdbResult<()> {
     Ok(())
 }
 
+#[cfg_api_versions(min = 520)]
+async fn test_set_versionstamped_value_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    // the stamp is written in place of the placeholder, the rest of the value is kept
+    const VALUE_KEY: &[u8] = b"test_versionstamp_value";
+    let mut value = b"stamp:".to_vec();
+    value.extend_from_slice(&[0xff; 10]);
+    value.extend_from_slice(b":end");
+
+    let trx = db.create_trx()?;
+    trx.set_versionstamped_value(VALUE_KEY, &value, 6);
+    let f_version = trx.get_versionstamp();
+    trx.commit().await?;
+    let versionstamp = f_version.await?;
+    assert_eq!(versionstamp.len(), 10);
+
+    let trx = db.create_trx()?;
+    let stored = trx.get(VALUE_KEY, false).await?.expect("value was set");
+    assert_eq!(stored.len(), value.len());
+    assert_eq!(&stored[..6], b"stamp:");
+    assert_eq!(&stored[6..16], &versionstamp[..]);
+    assert_eq!(&stored[16..], b":end");
+
+    Ok(())
+}
+
 async fn test_read_version_async() -> FdbResult<()> {
     let db = common::database().await?;
 
@@ -290,6 +963,30 @@ async fn test_set_read_version_async() -> FdbResult<()> {
     trx.set_read_version(0);
     assert!(trx.get(KEY, false).await.is_err());
 
+    // pinning the read version of a previous transaction reads the same snapshot
+    let trx = db.create_trx()?;
+    trx.set(KEY, b"before");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let version = trx.get_read_version().await?;
+    assert_eq!(trx.get(KEY, false).await?.as_deref(), Some(&b"before"[..]));
+
+    let writer = db.create_trx()?;
+    writer.set(KEY, b"after");
+    writer.commit().await?;
+
+    let pinned = db.create_trx()?;
+    pinned.set_read_version(version);
+    assert_eq!(pinned.get_read_version().await?, version);
+    assert_eq!(
+        pinned.get(KEY, false).await?.as_deref(),
+        Some(&b"before"[..])
+    );
+
+    let fresh = db.create_trx()?;
+    assert_eq!(fresh.get(KEY, false).await?.as_deref(), Some(&b"after"[..]));
+
     Ok(())
 }
 
@@ -339,6 +1036,119 @@ async fn test_metadata_version() -> FdbResult<()> {
     Ok(())
 }
 
+#[cfg_api_versions(min = 620)]
+async fn test_get_approximate_size() -> FdbResult<()> {
+    const KEY: &str = "test_get_approximate_size";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    let mut previous_size = trx.get_approximate_size().await?;
+    for i in 0..10 {
+        let key = format!("{}-{}", KEY, i);
+        trx.set(key.as_bytes(), common::random_str(100).as_bytes());
+
+        let size = trx.get_approximate_size().await?;
+        assert!(
+            size > previous_size,
+            "size did not grow after a mutation: {} <= {}",
+            size,
+            previous_size
+        );
+        previous_size = size;
+    }
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 630)]
+async fn test_get_conflicting_keys_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_get_conflicting_keys";
+    const OTHER: &[u8] = b"test_get_conflicting_keys_other";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set_option(options::TransactionOption::ReportConflictingKeys)?;
+    trx.get(KEY, false).await?;
+    trx.get(OTHER, false).await?;
+
+    // only KEY is written concurrently
+    make_dirty(&db, KEY).await?;
+
+    trx.set(OTHER, b"value");
+    let err = trx
+        .commit()
+        .await
+        .expect_err("the transaction should conflict");
+    assert_eq!(err.code(), 1020);
+
+    let conflicts = err.get_conflicting_keys().await?;
+    assert_eq!(conflicts, vec![KEY.to_vec()..[KEY, b"\x00"].concat()]);
+
+    // without the option, nothing is reported
+    let trx = db.create_trx()?;
+    trx.get(KEY, false).await?;
+    make_dirty(&db, KEY).await?;
+    trx.set(OTHER, b"value");
+    let err = trx
+        .commit()
+        .await
+        .expect_err("the transaction should conflict");
+    assert!(err.get_conflicting_keys().await?.is_empty());
+
+    Ok(())
+}
+
+async fn test_lock_aware_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_lock_aware";
+
+    // option codes as defined in fdb.options
+    assert_eq!(options::TransactionOption::LockAware.code(), 700);
+    assert_eq!(options::TransactionOption::ReadLockAware.code(), 702);
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set_lock_aware()?;
+    trx.set(KEY, b"lock-aware");
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    trx.set_read_lock_aware()?;
+    let value = trx.get(KEY, false).await?;
+    assert_eq!(value.as_deref(), Some(&b"lock-aware"[..]));
+
+    Ok(())
+}
+
+async fn test_options_set_async() -> FdbResult<()> {
+    use foundationdb::options::TransactionOption;
+
+    let db = common::database().await?;
+
+    let mut trx = db.create_trx()?;
+    assert!(trx.options_set().is_empty());
+    trx.set_option(TransactionOption::Timeout(1000))?;
+    trx.set_option(TransactionOption::CausalReadRisky)?;
+
+    let options = trx.options_set();
+    assert_eq!(options.len(), 2);
+    assert!(matches!(options[0].0, TransactionOption::Timeout(1000)));
+    assert_eq!(options[0].1, 1000i64.to_le_bytes());
+    assert!(matches!(options[1].0, TransactionOption::CausalReadRisky));
+    assert!(options[1].1.is_empty());
+
+    // raw options are not recorded
+    trx.set_raw_option(TransactionOption::CausalReadRisky.code(), None)?;
+    assert_eq!(trx.options_set().len(), 2);
+
+    trx.reset();
+    assert!(trx.options_set().is_empty());
+
+    Ok(())
+}
+
 async fn test_set_raw_option_async() -> FdbResult<()> {
     const KEY: &[u8] = b"test_set_raw_option_async";
     const RETRY_COUNT: usize = 5;
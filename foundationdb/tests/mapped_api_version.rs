@@ -0,0 +1,44 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+mod common;
+
+#[test]
+// the runtime API version can only be selected once per process, hence this separate binary
+fn test_mapped_range_api_version() {
+    #[cfg(any(feature = "fdb-7_1", feature = "fdb-7_3"))]
+    {
+        let network = unsafe {
+            foundationdb::api::FdbApiBuilder::default()
+                .set_runtime_version(700)
+                .build()
+                .expect("fdb api initialized")
+                .boot()
+                .expect("fdb network running")
+        };
+        assert_eq!(foundationdb::api::selected_api_version(), Some(700));
+        futures::executor::block_on(test_mapped_range_api_version_async()).expect("failed to run");
+        drop(network);
+    }
+}
+
+#[cfg(any(feature = "fdb-7_1", feature = "fdb-7_3"))]
+async fn test_mapped_range_api_version_async() -> foundationdb::FdbResult<()> {
+    use foundationdb::{tuple::pack, RangeOption};
+
+    let db = common::database().await?;
+    let trx = db.create_trx()?;
+
+    let mapper = pack(&("data", "{K[2]}", "{...}"));
+    let opt = RangeOption::from((&b"index"[..], &b"indey"[..]));
+    match trx.get_mapped_range(&opt, &mapper, 1, true).await {
+        Err(err) => assert_eq!(err.code(), 2203, "unexpected error: {}", err),
+        Ok(_) => panic!("get_mapped_range requires api version 710"),
+    }
+
+    Ok(())
+}
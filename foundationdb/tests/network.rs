@@ -0,0 +1,31 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+use foundationdb::*;
+
+mod common;
+
+#[test]
+fn test_network_stop() {
+    let network = unsafe { foundationdb::boot() };
+    futures::executor::block_on(test_network_stop_async()).expect("failed to run");
+    network.stop().expect("failed to stop network");
+}
+
+async fn test_network_stop_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test_network_stop";
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.set(KEY, common::random_str(10).as_bytes());
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    assert!(trx.get(KEY, false).await?.is_some());
+
+    Ok(())
+}
@@ -14,6 +14,7 @@ use futures::future;
 use futures::prelude::*;
 
 use std::borrow::Cow;
+use std::ops::ControlFlow;
 
 mod common;
 
@@ -21,8 +22,24 @@ mod common;
 fn test_range() {
     let _guard = unsafe { foundationdb::boot() };
     futures::executor::block_on(test_get_range_async()).expect("failed to run");
+    futures::executor::block_on(test_get_range_want_all()).expect("failed to run");
+    futures::executor::block_on(test_get_range_to_btree_map()).expect("failed to run");
     futures::executor::block_on(test_range_option_async()).expect("failed to run");
     futures::executor::block_on(test_get_ranges_async()).expect("failed to run");
+    futures::executor::block_on(test_get_ranges_prefetched()).expect("failed to run");
+    futures::executor::block_on(test_drop_ranges_mid_stream()).expect("failed to run");
+    futures::executor::block_on(test_scan_range()).expect("failed to run");
+    futures::executor::block_on(test_stream_range_to()).expect("failed to run");
+    futures::executor::block_on(test_partition_range()).expect("failed to run");
+    futures::executor::block_on(test_for_each_in_range()).expect("failed to run");
+    futures::executor::block_on(test_get_range_typed()).expect("failed to run");
+    futures::executor::block_on(test_get_range_decode()).expect("failed to run");
+    futures::executor::block_on(test_get_range_from_cursor()).expect("failed to run");
+    futures::executor::block_on(test_get_range_bounded_by_size()).expect("failed to run");
+    futures::executor::block_on(test_clear_range_starts_with()).expect("failed to run");
+    futures::executor::block_on(test_clear_ranges()).expect("failed to run");
+    futures::executor::block_on(test_get_range_reverse_order()).expect("failed to run");
+    futures::executor::block_on(test_get_range_reverse_limit()).expect("failed to run");
     #[cfg(any(
         feature = "fdb-6_3",
         feature = "fdb-7_0",
@@ -40,6 +57,9 @@ fn test_range() {
     {
         futures::executor::block_on(test_mapped_value()).expect("failed to run");
         futures::executor::block_on(test_mapped_values()).expect("failed to run");
+        futures::executor::block_on(test_mapped_multi()).expect("failed to run");
+        futures::executor::block_on(test_mapped_values_stream()).expect("failed to run");
+        futures::executor::block_on(test_mapped_from_plain_range()).expect("failed to run");
     }
 }
 
@@ -71,6 +91,17 @@ async fn test_get_range_async() -> FdbResult<()> {
         let range = trx.get_range(&opt, 1, false).await?;
         assert!(range.len() > 0);
         assert!(range.more());
+        let bytes: usize = range
+            .iter()
+            .map(|kv| kv.key().len() + kv.value().len())
+            .sum();
+        assert_eq!(range.byte_len(), bytes);
+        // every key is `key_begin`, a dash and 10 random characters, every value 10 characters
+        let kv_bytes = key_begin.len() + 1 + 10 + 10;
+        assert_eq!(
+            range.retained_bytes(),
+            range.len() * (std::mem::size_of::<foundationdb::future::FdbKeyValue>() + kv_bytes)
+        );
         let len = range.len();
         let mut i = 0;
         for kv in &range {
@@ -92,7 +123,62 @@ async fn test_get_range_async() -> FdbResult<()> {
             .into_iter()
             .collect::<Vec<_>>();
         assert_eq!(owned_asc, range.into_iter().rev().rev().collect::<Vec<_>>());
+
+        // owned values keep the underlying future alive and outlive the transaction
+        let owned = trx.get_range(&opt, 1, false).await?.into_iter();
+        assert_eq!(owned.len(), len);
+        let owned = owned.collect::<Vec<foundationdb::future::FdbValue>>();
+        drop(trx);
+        assert_eq!(owned.len(), len);
+        for kv in &owned {
+            assert!(kv.key().starts_with(key_begin.as_bytes()));
+            assert!(!kv.value().is_empty());
+        }
+    }
+
+    Ok(())
+}
+
+async fn test_get_range_want_all() -> FdbResult<()> {
+    use options::StreamingMode;
+
+    const N: usize = 100;
+    const BEGIN: &[u8] = b"test-range-want-all/";
+    const END: &[u8] = b"test-range-want-all0";
+
+    // codes of the FDBStreamingMode enum
+    let modes = [
+        (StreamingMode::WantAll, -2),
+        (StreamingMode::Iterator, -1),
+        (StreamingMode::Exact, 0),
+        (StreamingMode::Small, 1),
+        (StreamingMode::Medium, 2),
+        (StreamingMode::Large, 3),
+        (StreamingMode::Serial, 4),
+    ];
+    for (mode, code) in modes {
+        assert_eq!(mode.code(), code, "{:?}", mode);
+    }
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(BEGIN, END);
+    for i in 0..N {
+        let key = [BEGIN, format!("{:03}", i).as_bytes()].concat();
+        trx.set(&key, common::random_str(10).as_bytes());
     }
+    trx.commit().await?;
+
+    // a small range is transferred at once
+    let opt = RangeOption {
+        mode: StreamingMode::WantAll,
+        ..RangeOption::from((BEGIN, END))
+    };
+    let trx = db.create_trx()?;
+    let range = trx.get_range(&opt, 1, false).await?;
+    assert_eq!(range.len(), N);
+    assert!(!range.more());
 
     Ok(())
 }
@@ -134,6 +220,708 @@ async fn test_get_ranges_async() -> FdbResult<()> {
     Ok(())
 }
 
+async fn test_get_ranges_prefetched() -> FdbResult<()> {
+    const N: usize = 2000;
+    const PREFIX: &[u8] = b"test-ranges-prefetched/";
+    const END: &[u8] = b"test-ranges-prefetched0";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(PREFIX);
+    for i in 0..N {
+        let key = [PREFIX, format!("{:05}", i).as_bytes()].concat();
+        trx.set(&key, common::random_str(10).as_bytes());
+    }
+    trx.commit().await?;
+
+    // small pages, so that the range spans many of them
+    let opt = || RangeOption {
+        limit: Some(N),
+        mode: options::StreamingMode::Small,
+        ..RangeOption::from((PREFIX, END))
+    };
+
+    let trx = db.create_trx()?;
+    let serialized: Vec<Vec<u8>> = trx
+        .get_ranges_keyvalues(opt(), false)
+        .map_ok(|value| value.key().to_vec())
+        .try_collect()
+        .await?;
+    assert_eq!(serialized.len(), N);
+
+    let mut pages = 0;
+    let mut prefetched = Vec::new();
+    let mut ranges = trx.get_ranges_prefetched(opt(), false);
+    while let Some(values) = ranges.try_next().await? {
+        pages += 1;
+        prefetched.extend(values.iter().map(|value| value.key().to_vec()));
+        if pages == 1 {
+            // the next page was requested while the first one was being processed
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            let next = ranges.next().now_or_never();
+            let values = next
+                .expect("the second page should be ready")
+                .expect("the range spans several pages")?;
+            pages += 1;
+            prefetched.extend(values.iter().map(|value| value.key().to_vec()));
+        }
+    }
+    assert!(pages > 2, "{} pages", pages);
+    assert_eq!(prefetched, serialized);
+
+    Ok(())
+}
+
+async fn test_drop_ranges_mid_stream() -> FdbResult<()> {
+    use rand::Rng;
+
+    const N: usize = 1000;
+    const PREFIX: &[u8] = b"test-drop-ranges/";
+    const END: &[u8] = b"test-drop-ranges0";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(PREFIX);
+    for i in 0..N {
+        let key = [PREFIX, format!("{:05}", i).as_bytes()].concat();
+        trx.set(&key, common::random_str(10).as_bytes());
+    }
+    trx.commit().await?;
+
+    let opt = || RangeOption {
+        mode: options::StreamingMode::Small,
+        ..RangeOption::from((PREFIX, END))
+    };
+
+    let trx = db.create_trx()?;
+    let mut rng = rand::thread_rng();
+    let mut kept = Vec::new();
+    for round in 0..200 {
+        let mut ranges: std::pin::Pin<
+            Box<dyn Stream<Item = FdbResult<foundationdb::future::FdbValues>>>,
+        > = if round % 2 == 0 {
+            Box::pin(trx.get_ranges(opt(), false))
+        } else {
+            Box::pin(trx.get_ranges_prefetched(opt(), false))
+        };
+        for _ in 0..rng.gen_range(0..4) {
+            if let Some(values) = ranges.try_next().await? {
+                // pages and values taken out of them outlive the stream
+                if let Some(value) = values.into_iter().last() {
+                    kept.push(value);
+                }
+            }
+        }
+        if rng.gen_bool(0.5) {
+            // leave a page in flight, with its callback registered, when the stream is dropped
+            if let std::task::Poll::Ready(Some(values)) = futures::poll!(ranges.next()) {
+                values?;
+            }
+        }
+        drop(ranges);
+    }
+
+    for value in kept.iter() {
+        assert!(value.key().starts_with(PREFIX));
+        assert_eq!(value.value().len(), 10);
+    }
+
+    // the transaction is still usable once all those reads were cancelled
+    let count = trx
+        .get_ranges_keyvalues(opt(), false)
+        .try_fold(0, |count, _| future::ready(Ok(count + 1)))
+        .await?;
+    assert_eq!(count, N);
+
+    Ok(())
+}
+
+async fn test_scan_range() -> FdbResult<()> {
+    const N: usize = 1000;
+    const PREFIX: &[u8] = b"test-scan-range/";
+    const END: &[u8] = b"test-scan-range0";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(PREFIX, END);
+    for i in 0..N {
+        let key = [PREFIX, format!("{:04}", i).as_bytes()].concat();
+        trx.set(&key, common::random_str(10).as_bytes());
+    }
+    trx.commit().await?;
+
+    let opt = || RangeOption {
+        mode: options::StreamingMode::Small,
+        ..RangeOption::from((PREFIX, END))
+    };
+    // outlives the 5 seconds read window after the first page
+    async fn slow_scan(
+        ranges: impl Stream<Item = FdbResult<foundationdb::future::FdbValues>>,
+    ) -> FdbResult<Vec<Vec<u8>>> {
+        let mut ranges = std::pin::pin!(ranges);
+        let mut keys = Vec::new();
+        while let Some(values) = ranges.try_next().await? {
+            if keys.is_empty() {
+                std::thread::sleep(std::time::Duration::from_secs(6));
+            }
+            keys.extend(values.iter().map(|value| value.key().to_vec()));
+        }
+        Ok(keys)
+    }
+
+    let err = slow_scan(db.scan_range(opt(), false))
+        .await
+        .expect_err("the transaction should be too old");
+    assert_eq!(err.code(), 1007);
+
+    let keys = slow_scan(db.scan_range(opt(), true)).await?;
+    assert_eq!(keys.len(), N);
+    assert!(keys.windows(2).all(|w| w[0] < w[1]));
+
+    Ok(())
+}
+
+async fn test_stream_range_to() -> FdbResult<()> {
+    const N: usize = 1000;
+    let key_begin = "test-stream-range-";
+    let key_end = "test-stream-range.";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..N {
+        let key = format!("{}{:04}", key_begin, i);
+        trx.set(key.as_bytes(), format!("value-{}", i).as_bytes());
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()));
+
+    let mut buffer = futures::io::Cursor::new(Vec::new());
+    let count = trx
+        .stream_range_to(
+            opt.clone(),
+            false,
+            &mut buffer,
+            ExportFormat::LengthPrefixed,
+        )
+        .await
+        .expect("could not stream range");
+    assert_eq!(count, N as u64);
+
+    let bytes = buffer.into_inner();
+    let mut rest = bytes.as_slice();
+    let read_u32 = |rest: &mut &[u8]| {
+        let (len, tail) = rest.split_at(4);
+        *rest = tail;
+        u32::from_be_bytes(len.try_into().unwrap()) as usize
+    };
+    for i in 0..N {
+        let key_len = read_u32(&mut rest);
+        let (key, tail) = rest.split_at(key_len);
+        rest = tail;
+        let value_len = read_u32(&mut rest);
+        let (value, tail) = rest.split_at(value_len);
+        rest = tail;
+        assert_eq!(key, format!("{}{:04}", key_begin, i).as_bytes());
+        assert_eq!(value, format!("value-{}", i).as_bytes());
+    }
+    assert!(rest.is_empty());
+
+    let mut buffer = futures::io::Cursor::new(Vec::new());
+    let count = trx
+        .stream_range_to(opt, false, &mut buffer, ExportFormat::JsonLines)
+        .await
+        .expect("could not stream range");
+    assert_eq!(count, N as u64);
+    let lines = String::from_utf8(buffer.into_inner()).expect("invalid utf8");
+    assert_eq!(lines.lines().count(), N);
+    // "test-stream-range-0000" => "value-0"
+    assert_eq!(
+        lines.lines().next(),
+        Some(r#"{"key":"746573742d73747265616d2d72616e67652d30303030","value":"76616c75652d30"}"#)
+    );
+
+    Ok(())
+}
+
+async fn test_partition_range() -> FdbResult<()> {
+    let key_begin = "test-partition-range-";
+    let key_end = "test-partition-range.";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..100 {
+        let group = if i % 3 == 0 { "hot" } else { "cold" };
+        let key = format!("{}{}-{:03}", key_begin, group, i);
+        trx.set(key.as_bytes(), &[i as u8]);
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let hot_prefix = format!("{}hot-", key_begin);
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()));
+    let (hot, cold) = trx
+        .partition_range(opt, false, |key| key.starts_with(hot_prefix.as_bytes()))
+        .await?;
+
+    assert_eq!(hot.len(), 34);
+    assert_eq!(cold.len(), 66);
+    assert!(hot
+        .iter()
+        .all(|(key, value)| key.starts_with(hot_prefix.as_bytes()) && value[0] % 3 == 0));
+    assert!(cold
+        .iter()
+        .all(|(key, value)| !key.starts_with(hot_prefix.as_bytes()) && value[0] % 3 != 0));
+    assert!(hot.windows(2).all(|w| w[0].0 < w[1].0));
+
+    Ok(())
+}
+
+async fn test_for_each_in_range() -> FdbResult<()> {
+    let key_begin = "test-for-each-in-range-";
+    let key_end = "test-for-each-in-range.";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..100u8 {
+        let key = format!("{}{:03}", key_begin, i);
+        trx.set(key.as_bytes(), &[i]);
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()));
+
+    let mut sum = 0u64;
+    trx.for_each_in_range(opt.clone(), false, |_, value| {
+        sum += u64::from(value[0]);
+        ControlFlow::Continue(())
+    })
+    .await?;
+    assert_eq!(sum, (0..100).sum::<u64>());
+
+    // stop once the sum reaches a threshold
+    let (mut sum, mut seen) = (0u64, 0);
+    trx.for_each_in_range(opt, false, |key, value| {
+        assert!(key.starts_with(key_begin.as_bytes()));
+        sum += u64::from(value[0]);
+        seen += 1;
+        if sum >= 100 {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    })
+    .await?;
+    // 0 + 1 + ... + 14 = 105
+    assert_eq!((sum, seen), (105, 15));
+
+    Ok(())
+}
+
+async fn test_get_range_typed() -> FdbResult<()> {
+    const N: u32 = 100;
+    let subspace = tuple::Subspace::all().subspace(&"test-get-range-typed");
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_subspace_range(&subspace);
+    for i in 0..N {
+        trx.set(&subspace.pack(&i), &tuple::pack(&format!("name-{}", i)));
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let pairs: Vec<((String, u32), String)> = trx
+        .get_range_typed(RangeOption::from(&subspace), false)
+        .await
+        .expect("could not decode range");
+    assert_eq!(pairs.len(), N as usize);
+    for (i, ((prefix, id), name)) in (0..N).zip(pairs) {
+        assert_eq!(prefix, "test-get-range-typed");
+        assert_eq!(id, i);
+        assert_eq!(name, format!("name-{}", i));
+    }
+
+    // values are strings, not integers
+    let err = trx
+        .get_range_typed::<(String, u32), u32>(RangeOption::from(&subspace), false)
+        .await
+        .expect_err("values should not decode");
+    assert!(matches!(err, FdbBindingError::PackError(_)));
+    let source = std::error::Error::source(&err).expect("the PackError should be the source");
+    assert!(source.to_string().contains("value"));
+
+    Ok(())
+}
+
+async fn test_get_range_decode() -> FdbResult<()> {
+    const PREFIX: &[u8] = b"test-range-decode/";
+    const END: &[u8] = b"test-range-decode0";
+    let key = |i: u32| [PREFIX, &i.to_be_bytes()].concat();
+
+    let decode_u32 = |value: &[u8]| value.try_into().map(u32::from_le_bytes);
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(PREFIX, END);
+    for i in 0..100u32 {
+        trx.set(&key(i), &(i * 3).to_le_bytes());
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let pairs = trx
+        .get_range_decode(RangeOption::from((PREFIX, END)), false, decode_u32)
+        .await
+        .expect("all values are u32");
+    assert_eq!(pairs.len(), 100);
+    for (i, (k, value)) in pairs.into_iter().enumerate() {
+        assert_eq!(k, key(i as u32));
+        assert_eq!(value, i as u32 * 3);
+    }
+
+    // a value of the wrong length is reported with its key
+    trx.set(&key(42), b"not a u32");
+    let err = trx
+        .get_range_decode(RangeOption::from((PREFIX, END)), false, decode_u32)
+        .await
+        .expect_err("the value of key 42 is not a u32");
+    match err {
+        FdbBindingError::CustomError(err) => {
+            let err = err
+                .downcast_ref::<ValueDecodeError>()
+                .expect("a ValueDecodeError");
+            assert_eq!(err.key(), key(42).as_slice());
+        }
+        err => panic!("unexpected error {:?}", err),
+    }
+
+    Ok(())
+}
+
+async fn test_get_range_from_cursor() -> FdbResult<()> {
+    const N: usize = 10;
+    let key_begin = "test-range-cursor-";
+    let key_end = "test-range-cursor.";
+    let key = |i: usize| format!("{}{:02}", key_begin, i).into_bytes();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..N {
+        trx.set(&key(i), &[i as u8]);
+    }
+    trx.commit().await?;
+
+    // reads every page in its own transaction, passing the cursor around as bytes
+    async fn scan(
+        db: &Database,
+        opt: RangeOption<'_>,
+        page_size: usize,
+    ) -> FdbResult<Vec<Vec<Vec<u8>>>> {
+        let mut pages = Vec::new();
+        let mut token = Some(RangeCursor::from(opt).to_bytes());
+        while let Some(bytes) = token.take() {
+            let cursor = RangeCursor::from_bytes(&bytes).expect("invalid cursor");
+            let trx = db.create_trx()?;
+            let (values, next) = trx.get_range_from_cursor(&cursor, page_size, false).await?;
+            pages.push(values.iter().map(|kv| kv.key().to_vec()).collect());
+            token = next.map(|cursor| cursor.to_bytes());
+        }
+        Ok(pages)
+    }
+
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()));
+    let pages = scan(&db, opt.clone(), 6).await?;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0], (0..6).map(key).collect::<Vec<_>>());
+    assert_eq!(pages[1], (6..N).map(key).collect::<Vec<_>>());
+
+    // the limit spans pages, and the scan stops once it is reached
+    let opt = RangeOption {
+        limit: Some(7),
+        ..opt.rev()
+    };
+    let pages = scan(&db, opt, 4).await?;
+    assert_eq!(pages.len(), 2);
+    assert_eq!(pages[0], (6..N).rev().map(key).collect::<Vec<_>>());
+    assert_eq!(pages[1], (3..6).rev().map(key).collect::<Vec<_>>());
+
+    assert!(RangeCursor::from_bytes(b"not a cursor").is_err());
+
+    Ok(())
+}
+
+async fn test_get_range_bounded_by_size() -> FdbResult<()> {
+    const N: usize = 10;
+    const VALUE_SIZE: usize = 10_000;
+    let key_begin = "test-range-bounded-";
+    let key_end = "test-range-bounded.";
+    let key = |i: usize| format!("{}{:02}", key_begin, i).into_bytes();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..N {
+        trx.set(&key(i), &vec![i as u8; VALUE_SIZE]);
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()));
+    let (values, more) = trx
+        .get_range_bounded_by_size(opt.clone(), 3 * VALUE_SIZE, false)
+        .await?;
+    assert!(
+        more,
+        "the budget should stop the read before the end of the range"
+    );
+    assert!(values.len() >= 3 && values.len() < N);
+    for (i, kv) in values.iter().enumerate() {
+        assert_eq!(kv.key(), key(i).as_slice());
+        assert_eq!(kv.value(), vec![i as u8; VALUE_SIZE].as_slice());
+    }
+
+    // a budget larger than the range reads all of it
+    let (values, more) = trx
+        .get_range_bounded_by_size(opt, 2 * N * VALUE_SIZE, false)
+        .await?;
+    assert!(!more);
+    assert_eq!(values.len(), N);
+
+    Ok(())
+}
+
+async fn test_clear_range_starts_with() -> FdbResult<()> {
+    const PARENT: &[u8] = b"test-clear-starts-with/";
+    let key = |suffix: &[u8]| [PARENT, suffix].concat();
+
+    assert_eq!(strinc(b"a"), Some(b"b".to_vec()));
+    assert_eq!(strinc(b"a\xff\xff"), Some(b"b".to_vec()));
+    assert_eq!(strinc(b"\xff\xff"), None);
+    assert_eq!(strinc(b""), None);
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(PARENT);
+    for suffix in [&b"a"[..], b"a\x00", b"a\x7f", b"a\xff", b"a\xff\xff", b"b"] {
+        trx.set(&key(suffix), b"");
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(&key(b"a"));
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let parent_end = strinc(PARENT).unwrap();
+    let opt = RangeOption::from((PARENT, parent_end.as_slice()));
+    let remaining: Vec<Vec<u8>> = trx
+        .get_ranges_keyvalues(opt, false)
+        .map_ok(|kv| kv.key().to_vec())
+        .try_collect()
+        .await?;
+    assert_eq!(remaining, vec![key(b"b")]);
+
+    // a prefix within the special key space clears nothing rather than an inverted range
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(b"\xff\xff\xff");
+    trx.commit().await?;
+
+    Ok(())
+}
+
+async fn test_clear_ranges() -> FdbResult<()> {
+    const PARENT: &[u8] = b"test-clear-ranges/";
+    let key = |suffix: &[u8]| [PARENT, suffix].concat();
+    let (a, b, c, d, e) = (key(b"a"), key(b"b"), key(b"c"), key(b"d"), key(b"e"));
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range_starts_with(PARENT);
+    for k in [&a, &b, &c, &d, &e] {
+        trx.set(k, b"");
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_ranges(&[(&d, &e), (&a, &b), (&c, &c)]);
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let parent_end = strinc(PARENT).unwrap();
+    let opt = RangeOption::from((PARENT, parent_end.as_slice()));
+    let remaining: Vec<Vec<u8>> = trx
+        .get_ranges_keyvalues(opt, false)
+        .map_ok(|kv| kv.key().to_vec())
+        .try_collect()
+        .await?;
+    assert_eq!(remaining, vec![b.clone(), c.clone(), e.clone()]);
+
+    #[cfg(debug_assertions)]
+    {
+        use std::panic::{catch_unwind, AssertUnwindSafe};
+
+        let trx = db.create_trx()?;
+        let overlapping = catch_unwind(AssertUnwindSafe(|| {
+            trx.clear_ranges(&[(&a, &c), (&b, &d)]);
+        }));
+        assert!(overlapping.is_err());
+        let reversed = catch_unwind(AssertUnwindSafe(|| {
+            trx.clear_ranges(&[(&c, &a)]);
+        }));
+        assert!(reversed.is_err());
+    }
+
+    Ok(())
+}
+
+async fn test_get_range_reverse_order() -> FdbResult<()> {
+    let key_begin = "test-reverse-order-";
+    let key_end = "test-reverse-order.";
+    let key = |i: usize| format!("{}{}", key_begin, i).into_bytes();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..5 {
+        trx.set(&key(i), &[i as u8]);
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption::from((key_begin.as_bytes(), key_end.as_bytes())).rev();
+    let values = trx.get_range(&opt, 1, false).await?;
+
+    let borrowed: Vec<&[u8]> = values.iter().map(|kv| kv.key()).collect();
+    assert_eq!(
+        borrowed,
+        (0..5).rev().map(key).collect::<Vec<_>>(),
+        "reverse read should yield the largest key first"
+    );
+    for (kv, i) in values.iter().zip((0..5).rev()) {
+        assert_eq!(kv.pair(), (kv.key(), kv.value()));
+        assert_eq!(kv.pair(), (key(i).as_slice(), &[i as u8][..]));
+    }
+    assert_eq!(values.len(), 5);
+    assert!(!values.is_empty());
+    assert_eq!(values.first().map(|kv| kv.key()), Some(key(4).as_slice()));
+    assert_eq!(values.last().map(|kv| kv.key()), Some(key(0).as_slice()));
+
+    let empty = trx
+        .get_range(
+            &RangeOption::from((key_end.as_bytes(), key_end.as_bytes())),
+            1,
+            false,
+        )
+        .await?;
+    assert!(empty.is_empty());
+    assert!(empty.first().is_none() && empty.last().is_none());
+
+    let mut owned = values.into_iter();
+    assert_eq!(owned.next().unwrap().key(), key(4).as_slice());
+    assert_eq!(owned.next_back().unwrap().key(), key(0).as_slice());
+    let rest: Vec<Vec<u8>> = owned.map(|kv| kv.key().to_vec()).collect();
+    assert_eq!(rest, vec![key(3), key(2), key(1)]);
+
+    Ok(())
+}
+
+async fn test_get_range_reverse_limit() -> FdbResult<()> {
+    let key_begin = "test-reverse-limit-";
+    let key_end = "test-reverse-limit.";
+    let key = |i: usize| format!("{}{}", key_begin, i).into_bytes();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(key_begin.as_bytes(), key_end.as_bytes());
+    for i in 0..10 {
+        trx.set(&key(i), &[i as u8]);
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption {
+        limit: Some(3),
+        reverse: true,
+        ..RangeOption::from((key_begin.as_bytes(), key_end.as_bytes()))
+    };
+    let highest: Vec<Vec<u8>> = vec![key(9), key(8), key(7)];
+
+    // the limit applies from the end of the range when reading in reverse
+    let values = trx.get_range(&opt, 1, false).await?;
+    let keys: Vec<Vec<u8>> = values.iter().map(|kv| kv.key().to_vec()).collect();
+    assert_eq!(keys, highest);
+    let keys: Vec<Vec<u8>> = values.into_iter().map(|kv| kv.key().to_vec()).collect();
+    assert_eq!(keys, highest);
+
+    // same through the paging stream, reading small batches
+    let opt = RangeOption {
+        mode: options::StreamingMode::Small,
+        limit: Some(3),
+        ..opt
+    };
+    let keys: Vec<Vec<u8>> = trx
+        .get_ranges_keyvalues(opt, false)
+        .map_ok(|kv| kv.key().to_vec())
+        .try_collect()
+        .await?;
+    assert_eq!(keys, highest);
+
+    Ok(())
+}
+
+async fn test_get_range_to_btree_map() -> FdbResult<()> {
+    const BEGIN: &[u8] = b"test-range-btree-map/";
+    const END: &[u8] = b"test-range-btree-map0";
+    let key = |name: &str| [BEGIN, name.as_bytes()].concat();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(BEGIN, END);
+    for name in ["c", "a", "b"] {
+        trx.set(&key(name), format!("value-{}", name).as_bytes());
+    }
+    trx.commit().await?;
+
+    let expected = vec![
+        (key("a"), b"value-a".to_vec()),
+        (key("b"), b"value-b".to_vec()),
+        (key("c"), b"value-c".to_vec()),
+    ];
+    let trx = db.create_trx()?;
+    for reverse in [false, true] {
+        let opt = RangeOption {
+            reverse,
+            ..RangeOption::from((BEGIN, END))
+        };
+        let map = trx.get_range(&opt, 1, false).await?.to_btree_map();
+        assert_eq!(map.into_iter().collect::<Vec<_>>(), expected);
+    }
+
+    Ok(())
+}
+
 async fn test_range_option_async() -> FdbResult<()> {
     let db = common::database().await?;
 
@@ -283,12 +1071,31 @@ async fn test_get_range_split_points() -> FdbResult<()> {
         .get_range_split_points(key_begin.as_bytes(), key_end.as_bytes(), 100)
         .await?;
     assert!(splits.len() > 0);
+    assert!(!splits.is_empty());
+    // the split points start with the begin key and end with the end key
+    assert_eq!(
+        splits.first().map(|split| split.key()),
+        Some(key_begin.as_bytes())
+    );
+    assert_eq!(
+        splits.last().map(|split| split.key()),
+        Some(key_end.as_bytes())
+    );
+    for split in splits.iter() {
+        assert_eq!(as_bytes(split), split.key());
+    }
     for split in splits {
+        assert_eq!(as_bytes(&split), split.key());
         eprintln!("split point: {:?}", split.key());
     }
     Ok(())
 }
 
+#[cfg_api_versions(min = 700)]
+fn as_bytes(bytes: &impl AsRef<[u8]>) -> &[u8] {
+    bytes.as_ref()
+}
+
 #[cfg_api_versions(min = 710)]
 async fn test_mapped_value() -> FdbResult<()> {
     use foundationdb::tuple::{pack, Subspace};
@@ -407,6 +1214,214 @@ async fn test_mapped_values() -> FdbResult<()> {
     Ok(())
 }
 
+#[cfg_api_versions(min = 710)]
+async fn test_mapped_multi() -> FdbResult<()> {
+    use foundationdb::tuple::{pack, unpack, Element, Subspace};
+
+    let db = common::database().await?;
+
+    let data_subspace = Subspace::all().subspace(&("data"));
+    let index_subspace = Subspace::all().subspace(&("index"));
+    let number_of_records: i32 = 20;
+
+    clear_mapped_data(&db, &data_subspace, &index_subspace).await;
+
+    let blue_counter =
+        setup_mapped_data(&db, &data_subspace, &index_subspace, number_of_records).await?;
+
+    let t = db.create_trx()?;
+    let colors = ["blue", "brown"];
+    let ranges = colors
+        .iter()
+        .map(|color| RangeOption::from(&index_subspace.subspace(color)))
+        .collect();
+    let mapper = pack(&("data", "{K[2]}", "{...}"));
+
+    let results = t.get_mapped_multi(ranges, &mapper, false).await?;
+    assert_eq!(results.len(), colors.len());
+
+    for (color, mapped_key_values) in colors.iter().zip(results.iter()) {
+        assert!(!mapped_key_values.is_empty());
+
+        // each result is the first batch of its own range: a prefix of the whole range, in order
+        let parent_keys: Vec<Vec<u8>> = t
+            .get_mapped_ranges_keyvalues(
+                RangeOption::from(&index_subspace.subspace(color)),
+                &mapper,
+                false,
+            )
+            .map_ok(|mapped_value| mapped_value.parent_key().to_vec())
+            .try_collect()
+            .await?;
+        if *color == "blue" {
+            assert_eq!(parent_keys.len() as i32, blue_counter);
+        }
+        assert!(mapped_key_values.len() <= parent_keys.len());
+        assert!(mapped_key_values
+            .iter()
+            .map(|mapped_key_value| mapped_key_value.parent_key())
+            .eq(parent_keys
+                .iter()
+                .take(mapped_key_values.len())
+                .map(Vec::as_slice)));
+
+        for mapped_key_value in mapped_key_values.iter() {
+            let parent_key: Vec<Element> =
+                unpack(mapped_key_value.parent_key()).expect("could not unpack index key");
+            assert!(parent_key.starts_with(&[
+                Element::String(Cow::from("index")),
+                Element::String(Cow::from(*color))
+            ]));
+            assert_eq!(mapped_key_value.key_values().len(), 2);
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 710)]
+async fn test_mapped_values_stream() -> FdbResult<()> {
+    use foundationdb::tuple::{pack, unpack, Element, Subspace};
+
+    let db = common::database().await?;
+
+    let data_subspace = Subspace::all().subspace(&("data"));
+    let index_subspace = Subspace::all().subspace(&("index"));
+    let number_of_records: i32 = 1_000;
+
+    clear_mapped_data(&db, &data_subspace, &index_subspace).await;
+
+    let blue_counter =
+        setup_mapped_data(&db, &data_subspace, &index_subspace, number_of_records).await?;
+
+    let t = db.create_trx()?;
+    let range_option = RangeOption {
+        limit: Some(100),
+        ..RangeOption::from(&index_subspace.subspace(&("blue")))
+    };
+    let mapper = pack(&("data", "{K[2]}", "{...}"));
+
+    // the limit applies to the whole stream, not to each batch
+    let limited = t
+        .get_mapped_ranges_keyvalues(range_option.clone(), &mapper, false)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(limited.len() as i32, blue_counter.min(100));
+
+    // without a limit, small batches force the stream to page through the whole index
+    let unlimited_option = RangeOption {
+        mode: options::StreamingMode::Small,
+        ..RangeOption::from(&index_subspace.subspace(&("blue")))
+    };
+    let batches = t
+        .get_mapped_ranges(unlimited_option.clone(), &mapper, false)
+        .try_fold(0, |batches, _| future::ok(batches + 1))
+        .await?;
+    assert!(batches > 1, "expected several batches, got {}", batches);
+
+    let mapped_values = t
+        .get_mapped_ranges_keyvalues(unlimited_option, &mapper, false)
+        .try_collect::<Vec<_>>()
+        .await?;
+    assert_eq!(mapped_values.len() as i32, blue_counter);
+    assert!(mapped_values
+        .windows(2)
+        .all(|pair| pair[0].parent_key() < pair[1].parent_key()));
+    assert_eq!(
+        limited
+            .iter()
+            .map(|mapped_value| mapped_value.parent_key())
+            .collect::<Vec<_>>(),
+        mapped_values[..limited.len()]
+            .iter()
+            .map(|mapped_value| mapped_value.parent_key())
+            .collect::<Vec<_>>()
+    );
+
+    for mapped_value in mapped_values.iter() {
+        // every index entry is joined with the two records sharing its primary key
+        let parent_key: Vec<Element> =
+            unpack(mapped_value.parent_key()).expect("could not unpack index key");
+        assert!(parent_key.starts_with(&[
+            Element::String(Cow::from("index")),
+            Element::String(Cow::from("blue"))
+        ]));
+        let primary_key = parent_key[2].clone();
+
+        let key_values = mapped_value.key_values();
+        assert_eq!(key_values.len(), 2);
+        for kv in key_values {
+            let key: Vec<Element> = unpack(kv.key()).expect("could not unpack key");
+            assert_eq!(
+                &key[..2],
+                &[Element::String(Cow::from("data")), primary_key.clone()]
+            );
+        }
+    }
+
+    // a mapper without any placeholder is rejected before reading
+    let bad_mapper = pack(&("data", "{...}"));
+    let result = t
+        .get_mapped_ranges_keyvalues(range_option, &bad_mapper, false)
+        .try_collect::<Vec<_>>()
+        .await;
+    assert!(matches!(result, Err(err) if err.code() == 2000));
+
+    Ok(())
+}
+
+#[cfg_api_versions(min = 710)]
+async fn test_mapped_from_plain_range() -> FdbResult<()> {
+    use foundationdb::mapped_key_values::MappedKeyValues;
+
+    const N: usize = 50;
+    const PREFIX: &[u8] = b"test-mapped-from-plain/";
+    const END: &[u8] = b"test-mapped-from-plain0";
+    let key = |i: usize| [PREFIX, format!("{:02}", i).as_bytes()].concat();
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    trx.clear_range(PREFIX, END);
+    for i in 0..N {
+        trx.set(&key(i), &i.to_be_bytes());
+    }
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let opt = RangeOption {
+        limit: Some(N - 10),
+        ..RangeOption::from((PREFIX, END))
+    };
+    let values = trx.get_range(&opt, 1, false).await?;
+    let expected: Vec<(Vec<u8>, Vec<u8>)> = values
+        .iter()
+        .map(|kv| (kv.key().to_vec(), kv.value().to_vec()))
+        .collect();
+    assert_eq!(expected.len(), N - 10);
+
+    let mapped = MappedKeyValues::from(values);
+    assert!(mapped.more());
+    assert_eq!(mapped.len(), N - 10);
+    for (i, mapped_key_value) in mapped.iter().enumerate() {
+        assert_eq!(mapped_key_value.parent_key(), key(i).as_slice());
+        assert_eq!(mapped_key_value.parent_value(), &i.to_be_bytes());
+        assert!(mapped_key_value.key_values().is_empty());
+        assert!(mapped_key_value.begin_range().is_empty());
+        assert!(mapped_key_value.end_range().is_empty());
+    }
+
+    // the owned values outlive the wrapper they were taken from
+    let owned: Vec<_> = mapped.into_iter().collect();
+    for (mapped_value, (key, value)) in owned.iter().zip(expected.iter()) {
+        assert_eq!(mapped_value.parent_key(), key.as_slice());
+        assert_eq!(mapped_value.parent_value(), value.as_slice());
+        assert!(mapped_value.key_values().is_empty());
+    }
+
+    Ok(())
+}
+
 #[cfg_api_versions(min = 710)]
 async fn setup_mapped_data(
     db: &Database,
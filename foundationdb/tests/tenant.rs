@@ -10,6 +10,8 @@ fn test_tenant() {
     {
         futures::executor::block_on(test_tenant_management()).expect("failed to run");
         futures::executor::block_on(test_tenant_run()).expect("failed to run");
+        futures::executor::block_on(test_tenant_create_trx()).expect("failed to run");
+        futures::executor::block_on(test_list_tenants_paged()).expect("failed to run");
     }
 }
 
@@ -133,3 +135,101 @@ async fn test_tenant_run() -> foundationdb::FdbResult<()> {
 
     Ok(())
 }
+
+#[cfg(all(
+    any(feature = "fdb-7_1", feature = "fdb-7_3"),
+    feature = "tenant-experimental"
+))]
+async fn test_tenant_create_trx() -> foundationdb::FdbResult<()> {
+    const KEY: &[u8] = b"test_tenant_create_trx";
+
+    let tenant_name = format!(
+        "tenant-trx-{:?}",
+        std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+    );
+    let db = common::database().await?;
+    foundationdb::tenant::TenantManagement::create_tenant(&db, tenant_name.as_bytes())
+        .await
+        .expect("could not create tenant");
+    let tenant = db
+        .open_tenant(tenant_name.as_bytes())
+        .expect("could not open tenant");
+
+    let trx = db.create_trx()?;
+    trx.clear(KEY);
+    trx.commit().await?;
+
+    let trx = tenant.create_trx()?;
+    trx.set(KEY, tenant_name.as_bytes());
+    trx.commit().await?;
+
+    // the key is confined to the tenant
+    let trx = tenant.create_trx()?;
+    assert_eq!(
+        trx.get(KEY, false).await?.as_deref(),
+        Some(tenant_name.as_bytes())
+    );
+    let trx = db.create_trx()?;
+    assert!(trx.get(KEY, false).await?.is_none());
+
+    // remove key so that we can delete tenant
+    let trx = tenant.create_trx()?;
+    trx.clear(KEY);
+    trx.commit().await?;
+    foundationdb::tenant::TenantManagement::delete_tenant(&db, tenant_name.as_bytes())
+        .await
+        .expect("could not delete tenant");
+
+    Ok(())
+}
+
+#[cfg(all(
+    any(feature = "fdb-7_1", feature = "fdb-7_3"),
+    feature = "tenant-experimental"
+))]
+async fn test_list_tenants_paged() -> foundationdb::FdbResult<()> {
+    use foundationdb::tenant::TenantManagement;
+    use futures::TryStreamExt;
+
+    let prefix = format!(
+        "tenant-paged-{:?}-",
+        std::time::SystemTime::now()
+            .duration_since(std::time::SystemTime::UNIX_EPOCH)
+            .unwrap()
+            .as_millis()
+    );
+    let names: Vec<String> = (0..7).map(|i| format!("{}{}", prefix, i)).collect();
+
+    let db = common::database().await?;
+    for name in names.iter() {
+        TenantManagement::create_tenant(&db, name.as_bytes())
+            .await
+            .expect("could not create tenant");
+    }
+
+    // a page size of 2 needs several reads to go through the created tenants
+    let tenants: Vec<_> = TenantManagement::list_tenants_paged(&db, 2)
+        .try_collect()
+        .await
+        .expect("could not list tenants");
+    let listed: Vec<&String> = tenants
+        .iter()
+        .map(|(name, _)| name)
+        .filter(|name| name.starts_with(&prefix))
+        .collect();
+    assert_eq!(listed, names.iter().collect::<Vec<_>>());
+
+    let all = TenantManagement::list_tenant(&db, b"", b"", None).await?;
+    assert_eq!(tenants.len(), all.len());
+
+    for name in names.iter() {
+        TenantManagement::delete_tenant(&db, name.as_bytes())
+            .await
+            .expect("could not delete tenant");
+    }
+
+    Ok(())
+}
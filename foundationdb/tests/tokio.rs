@@ -24,6 +24,7 @@ fn test_tokio_send() {
         do_run_with_transaction_limits().await;
         do_trx().await;
         do_run_with_custom_error().await;
+        do_cloned_database().await;
     });
 }
 
@@ -96,6 +97,70 @@ async fn do_transact() {
     });
 }
 
+async fn do_cloned_database() {
+    const PREFIX: &[u8] = b"test-tokio-cloned-database/";
+
+    let db = foundationdb::Database::new_compat(None)
+        .await
+        .expect("failed to open fdb");
+
+    let tasks: Vec<_> = (0..100u32)
+        .map(|i| {
+            let db = db.clone();
+            tokio::spawn(async move {
+                let key = [PREFIX, &i.to_be_bytes()].concat();
+                db.run(|trx, _maybe_committed| {
+                    let key = key.clone();
+                    async move {
+                        trx.set(&key, b"cloned");
+                        Ok(())
+                    }
+                })
+                .await
+                .expect("failed to run");
+            })
+        })
+        .collect();
+    for task in tasks {
+        task.await.expect("task panicked");
+    }
+
+    let trx = db.create_trx().expect("failed to create trx");
+    let range = trx
+        .get_range(
+            &RangeOption::from((PREFIX, &b"test-tokio-cloned-database0"[..])),
+            1,
+            false,
+        )
+        .await
+        .expect("failed to read");
+    assert_eq!(range.len(), 100);
+
+    // clones share the native database, and thus its options
+    #[cfg(any(
+        feature = "fdb-6_1",
+        feature = "fdb-6_2",
+        feature = "fdb-6_3",
+        feature = "fdb-7_0",
+        feature = "fdb-7_1",
+        feature = "fdb-7_3"
+    ))]
+    {
+        let clone = db.clone();
+        clone
+            .set_option(options::DatabaseOption::TransactionTimeout(10))
+            .expect("failed to set timeout");
+        drop(clone);
+        let trx = db.create_trx().expect("failed to create trx");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        let err = trx
+            .get(PREFIX, false)
+            .await
+            .expect_err("the transaction should have timed out");
+        assert_eq!(err.code(), 1031);
+    }
+}
+
 async fn do_trx() {
     let db = Arc::new(
         foundationdb::Database::new_compat(None)
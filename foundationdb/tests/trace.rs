@@ -0,0 +1,102 @@
+// Copyright 2018 foundationdb-rs developers, https://github.com/Clikengo/foundationdb-rs/graphs/contributors
+//
+// Licensed under the Apache License, Version 2.0, <LICENSE-APACHE or
+// http://apache.org/licenses/LICENSE-2.0> or the MIT license <LICENSE-MIT or
+// http://opensource.org/licenses/MIT>, at your option. This file may not be
+// copied, modified, or distributed except according to those terms.
+
+#![cfg(feature = "tracing")]
+
+use foundationdb::*;
+use std::fmt;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Metadata, Subscriber};
+
+mod common;
+
+type RecordedSpan = (&'static str, Vec<(&'static str, String)>);
+
+/// Records the name and initial fields of every span created.
+#[derive(Default, Clone)]
+struct SpanRecorder {
+    spans: Arc<Mutex<Vec<RecordedSpan>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+struct FieldVisitor(Vec<(&'static str, String)>);
+
+impl Visit for FieldVisitor {
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.0.push((field.name(), format!("{:?}", value)));
+    }
+}
+
+impl Subscriber for SpanRecorder {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = FieldVisitor(Vec::new());
+        span.record(&mut fields);
+        self.spans
+            .lock()
+            .unwrap()
+            .push((span.metadata().name(), fields.0));
+        Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, _event: &Event<'_>) {}
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[test]
+fn test_tracing() {
+    let _guard = unsafe { foundationdb::boot() };
+    futures::executor::block_on(test_retry_spans_async()).expect("failed to run");
+}
+
+async fn test_retry_spans_async() -> FdbResult<()> {
+    let db = common::database().await?;
+
+    let recorder = SpanRecorder::default();
+    let attempts = AtomicUsize::new(0);
+    let _dispatch = tracing::subscriber::set_default(recorder.clone());
+
+    db.run(|trx, _maybe_committed| {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+        async move {
+            trx.set(b"test-tracing", b"");
+            if attempt == 0 {
+                // not_committed, retried by `run`
+                return Err(FdbError::from_code(1020).into());
+            }
+            Ok(())
+        }
+    })
+    .await
+    .expect("could not run the transaction");
+
+    let spans = recorder.spans.lock().unwrap();
+    let has_span = |name: &str, field: (&str, &str)| {
+        spans.iter().any(|(span, fields)| {
+            *span == name && fields.iter().any(|(k, v)| (*k, v.as_str()) == field)
+        })
+    };
+    assert!(has_span("fdb.on_error", ("error_code", "1020")));
+    assert!(has_span("fdb.attempt", ("retry_count", "0")));
+    assert!(has_span("fdb.attempt", ("retry_count", "1")));
+    assert!(spans.iter().any(|(span, _)| *span == "fdb.commit"));
+
+    Ok(())
+}
@@ -27,6 +27,57 @@ fn test_tuples() {
 
     eprintln!("creating directories");
     futures::executor::block_on(test_subspace_with_versionstamp(&db));
+
+    eprintln!("initializing subspaces");
+    futures::executor::block_on(test_init_subspace_if_empty(&db));
+
+    eprintln!("clearing subspaces");
+    futures::executor::block_on(test_clear_subspace(&db));
+
+    eprintln!("streaming subspaces");
+    futures::executor::block_on(test_subspace_range_stream(&db));
+}
+
+#[test]
+fn test_derive_tuple() {
+    use foundationdb::tuple::{pack, unpack, TuplePack, TupleUnpack};
+
+    #[derive(Debug, PartialEq, TuplePack, TupleUnpack)]
+    struct UserKey {
+        tenant: String,
+        id: i64,
+        tags: Vec<String>,
+    }
+
+    #[derive(Debug, PartialEq, TuplePack, TupleUnpack)]
+    struct Cached<T> {
+        value: T,
+        #[tuple(skip)]
+        hits: u32,
+    }
+
+    #[derive(Debug, PartialEq, TuplePack, TupleUnpack)]
+    struct Pair(i64, bool);
+
+    let key = UserKey {
+        tenant: "acme".to_string(),
+        id: 42,
+        tags: vec!["admin".to_string()],
+    };
+    let packed = pack(&key);
+    assert_eq!(packed, pack(&("acme", 42, vec!["admin"])));
+    assert_eq!(unpack::<UserKey>(&packed).unwrap(), key);
+
+    // derived structs nest like tuples
+    let cached = Cached {
+        value: key,
+        hits: 3,
+    };
+    let packed = pack(&(&cached, Pair(-1, true)));
+    let (unpacked, pair): (Cached<UserKey>, Pair) = unpack(&packed).unwrap();
+    assert_eq!(pair, Pair(-1, true));
+    assert_eq!(unpacked.value, cached.value);
+    assert_eq!(unpacked.hits, 0);
 }
 
 async fn test_subspace_with_versionstamp(db: &Database) {
@@ -97,3 +148,141 @@ async fn test_subspace_with_versionstamp(db: &Database) {
     assert_eq!(value.as_deref(), Some(b"hello2".as_ref()));
     trx.commit().await.expect("cannot commit");
 }
+
+async fn test_init_subspace_if_empty(db: &Database) {
+    let subspace = Subspace::from("init");
+    let defaults = [
+        (subspace.pack(&"a"), b"1".to_vec()),
+        (subspace.pack(&"b"), b"2".to_vec()),
+    ];
+
+    // two initializers racing on the same empty subspace
+    let trx1 = db.create_trx().expect("cannot create txn");
+    let trx2 = db.create_trx().expect("cannot create txn");
+    assert!(trx1
+        .init_subspace_if_empty(&subspace, &defaults[..1])
+        .await
+        .expect("cannot init subspace"));
+    assert!(trx2
+        .init_subspace_if_empty(&subspace, &defaults[1..])
+        .await
+        .expect("cannot init subspace"));
+
+    trx1.commit().await.expect("could not commit");
+    let err = trx2.commit().await.expect_err("concurrent init committed");
+    assert!(err.is_retryable_not_committed());
+
+    // the second initializer retries and notices the subspace is no longer empty
+    let trx2 = err.on_error().await.expect("cannot retry");
+    assert!(!trx2
+        .init_subspace_if_empty(&subspace, &defaults[1..])
+        .await
+        .expect("cannot init subspace"));
+    trx2.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    let values = trx
+        .get_range(&(&subspace).into(), 1, false)
+        .await
+        .expect("cannot read subspace");
+    assert_eq!(values.len(), 1);
+    assert_eq!(values[0].key(), defaults[0].0.as_slice());
+}
+
+async fn test_clear_subspace(db: &Database) {
+    let root = Subspace::from("clear");
+    let u = root.subspace(&"u");
+    let v = root.subspace(&"v");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    trx.set(&u.pack(&"x"), b"ux");
+    trx.set(&u.pack(&("y", 1)), b"uy");
+    trx.set(&v.pack(&"x"), b"vx");
+    trx.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    trx.clear_subspace_range(&u);
+    trx.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    assert_eq!(
+        trx.get(&u.pack(&"x"), false).await.unwrap().as_deref(),
+        None
+    );
+    assert_eq!(
+        trx.get(&u.pack(&("y", 1)), false).await.unwrap().as_deref(),
+        None
+    );
+    assert_eq!(
+        trx.get(&v.pack(&"x"), false).await.unwrap().as_deref(),
+        Some(b"vx".as_ref())
+    );
+
+    // partial tuple prefix, including the key packing the prefix itself
+    let trx = db.create_trx().expect("cannot create txn");
+    trx.set(&root.pack(&("w",)), b"w");
+    trx.set(&root.pack(&("w", "x")), b"wx");
+    trx.set(&root.pack(&("w\0",)), b"w0");
+    trx.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    trx.clear_starts_with(&("clear", "w"));
+    trx.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    assert_eq!(
+        trx.get(&root.pack(&("w",)), false)
+            .await
+            .unwrap()
+            .as_deref(),
+        None
+    );
+    assert_eq!(
+        trx.get(&root.pack(&("w", "x")), false)
+            .await
+            .unwrap()
+            .as_deref(),
+        None
+    );
+    assert_eq!(
+        trx.get(&root.pack(&("w\0",)), false)
+            .await
+            .unwrap()
+            .as_deref(),
+        Some(b"w0".as_ref())
+    );
+    assert_eq!(
+        trx.get(&v.pack(&"x"), false).await.unwrap().as_deref(),
+        Some(b"vx".as_ref())
+    );
+}
+
+async fn test_subspace_range_stream(db: &Database) {
+    let subspace = Subspace::from("range-stream");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    // inserted out of order, read back in key order
+    for (id, name) in [(3, "c"), (1, "a"), (2, "b")] {
+        trx.set(&subspace.pack(&(id, name)), name.as_bytes());
+    }
+    trx.set(&subspace.pack(&(4, "d", "extra")), b"");
+    trx.commit().await.expect("could not commit");
+
+    let trx = db.create_trx().expect("cannot create txn");
+    let pairs: Vec<_> = subspace
+        .range_stream::<(i64, String)>(&trx, false)
+        .collect()
+        .await;
+    assert_eq!(pairs.len(), 4);
+    for ((id, expected), pair) in [(1, "a"), (2, "b"), (3, "c")].into_iter().zip(&pairs) {
+        let ((key_id, key_name), value) = pair.as_ref().expect("could not unpack key");
+        assert_eq!(*key_id, id);
+        assert_eq!(key_name, expected);
+        assert_eq!(value.value(), expected.as_bytes());
+    }
+    // the last key does not unpack as (i64, String)
+    assert!(matches!(
+        pairs[3],
+        Err(foundationdb::FdbBindingError::PackError(_))
+    ));
+}
@@ -6,6 +6,8 @@
 // copied, modified, or distributed except according to those terms.
 
 use foundationdb::*;
+use futures::StreamExt;
+use std::time::Duration;
 
 mod common;
 
@@ -14,6 +16,9 @@ fn test_watch() {
     let _guard = unsafe { foundationdb::boot() };
     futures::executor::block_on(test_watch_async()).expect("failed to run");
     futures::executor::block_on(test_watch_without_commit_async()).expect("failed to run");
+    futures::executor::block_on(test_watch_with_timeout_async()).expect("failed to run");
+    futures::executor::block_on(test_watch_stream_async()).expect("failed to run");
+    futures::executor::block_on(test_watch_cancel_async()).expect("failed to run");
 }
 
 async fn test_watch_async() -> FdbResult<()> {
@@ -53,3 +58,86 @@ async fn test_watch_without_commit_async() -> FdbResult<()> {
 
     Ok(())
 }
+
+async fn test_watch_with_timeout_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test-watch-3";
+
+    let db = common::database().await?;
+
+    eprintln!("setting watch with timeout");
+    let trx = db.create_trx()?;
+    let watch = trx.watch_with_timeout(KEY, Duration::from_millis(500));
+    trx.commit().await?;
+    eprintln!("watch committed");
+
+    // nobody is writing the key, the watch must time out
+    assert!(!watch.await?);
+
+    eprintln!("setting watch with timeout, then writing value");
+    let trx = db.create_trx()?;
+    let watch = trx.watch_with_timeout(KEY, Duration::from_secs(30));
+    trx.commit().await?;
+
+    let trx = db.create_trx()?;
+    let value = common::random_str(10);
+    trx.set(KEY, value.as_bytes());
+    trx.commit().await?;
+
+    assert!(watch.await?);
+
+    Ok(())
+}
+
+async fn test_watch_stream_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test-watch-stream";
+
+    let db = common::database().await?;
+
+    eprintln!("setting watch stream");
+    let mut notifications = Box::pin(db.watch_stream(KEY).await?);
+
+    for _ in 0..2 {
+        eprintln!("writing value");
+        let trx = db.create_trx()?;
+        let value = common::random_str(10);
+        trx.set(KEY, value.as_bytes());
+        trx.commit().await?;
+
+        notifications
+            .next()
+            .await
+            .expect("the stream should not end")?;
+    }
+
+    // dropping the stream cancels the watch re-armed after the last notification
+    drop(notifications);
+
+    Ok(())
+}
+
+async fn test_watch_cancel_async() -> FdbResult<()> {
+    const KEY: &[u8] = b"test-watch-cancel";
+
+    let db = common::database().await?;
+
+    let trx = db.create_trx()?;
+    let watch = trx.watch(KEY);
+    trx.commit().await?;
+
+    // nobody is writing the key, the watch would wait forever
+    watch.cancel();
+    let err = watch.await.expect_err("a cancelled watch must fail");
+    assert_eq!(err.code(), 1101);
+
+    // cancelling a watch that already fired is a no-op
+    let trx = db.create_trx()?;
+    let mut watch = trx.watch(KEY);
+    trx.commit().await?;
+    let trx = db.create_trx()?;
+    trx.set(KEY, common::random_str(10).as_bytes());
+    trx.commit().await?;
+    (&mut watch).await?;
+    watch.cancel();
+
+    Ok(())
+}